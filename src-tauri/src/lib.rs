@@ -1,11 +1,41 @@
 mod network;
 
 use network::{
-    get_network_interfaces, get_routing_table, add_route, delete_route,
-    flush_routes, set_default_gateway, run_network_command, ping_host,
-    check_internet, fping_scan, get_bloatware_candidates, remove_bloatware,
-    clear_cache_targets, get_battery_report,
+    NetworkError, get_network_interfaces, get_routing_table, add_route,
+    delete_route, flush_routes, set_default_gateway, run_network_command,
+    ping_host, check_internet, fping_scan, get_bloatware_candidates,
+    remove_bloatware, clear_cache_targets, get_battery_report,
+    add_firewall_rule, get_proxy_settings, set_proxy_settings, run_speed_test,
+    save_route_profile, list_route_profiles, apply_route_profile,
+    get_default_gateway, get_allowed_commands, get_ip_configuration,
+    run_network_diagnostics, cancel_fping_scan, get_route_audit_log,
+    get_runtime_capabilities, repair_network_stack, check_connectivity,
+    start_gateway_monitor, stop_gateway_monitor, measure_service_latency,
+    test_connection, renew_dhcp_lease, remove_bloatware_category,
+    reinstall_appx_package, get_scheduled_tasks, set_scheduled_task_state,
+    get_services, set_service_state, get_startup_items,
+    set_startup_item_enabled, reverse_dns, discover_lan_devices,
+    lookup_mac_vendor, get_dns_cache, flush_dns_cache,
+    monitor_connection_quality, stop_connection_quality_monitor, set_route_metric, find_route_for_destination,
+    export_interfaces, generate_diagnostics_bundle, get_primary_interface,
+    delete_routes_to, flush_temporary_routes, find_optimal_mtu,
+    get_ipv6_neighbors, get_firewall_profiles, set_firewall_profile,
+    benchmark_dns, get_power_plans, set_active_power_plan, get_battery_status,
+    ping_detailed, clear_network_caches, detect_ip_conflicts,
+    backup_network_config, restore_network_config, trace_path_to,
+    get_interface_addresses, is_port_open, get_persistent_routes,
+    set_ipv6_enabled, reset_interface, detect_captive_portal,
+    get_windows_version, get_processes, kill_process, get_system_uptime,
+    get_disk_usage, get_cache_target_details, compute_subnet_info,
+    get_preferences, set_preferences, preview_default_gateway_change,
+    get_default_gateways, flush_arp_cache, get_process_network_usage,
+    add_temporary_route, cancel_temporary_route, resolve_host, audit_routes,
+    monitor_dns, stop_dns_monitor, get_network_event_log, test_path_mtu, get_effective_dns,
+    run_network_commands, cancel_cache_cleanup, export_bloatware_report,
+    can_perform, snapshot_routes, diff_routes, ping_percentiles, set_doh,
+    check_stack_health, get_interface_metric, set_interface_metric,
 };
+use serde::Serialize;
 #[cfg(target_os = "windows")]
 use std::os::windows::process::CommandExt;
 #[cfg(target_os = "windows")]
@@ -22,11 +52,14 @@ const REQUIRED_COMMANDS: [&str; 5] = ["route", "netsh", "ipconfig", "ping", "pow
 const WEBVIEW2_CLIENT_GUID: &str = "{F3017226-FE2A-4295-8BDF-00C3A9A7E4C5}";
 #[cfg(target_os = "windows")]
 const DEV_DISABLE_ERROR_DIALOG_ENV: &str = "SRP_DEV_NO_DIALOG";
+#[cfg(target_os = "windows")]
+const MIN_WINDOWS_BUILD_ENV: &str = "SRP_MIN_BUILD";
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    if let Err(reason) = validate_runtime_environment() {
-        block_app_start(&reason);
+    match validate_runtime_environment() {
+        Ok(degraded) => network::set_degraded_mode(degraded),
+        Err(reason) => block_app_start(&reason),
     }
 
     tauri::Builder::default()
@@ -46,45 +79,237 @@ pub fn run() {
             remove_bloatware,
             clear_cache_targets,
             get_battery_report,
+            add_firewall_rule,
+            get_proxy_settings,
+            set_proxy_settings,
+            run_speed_test,
+            save_route_profile,
+            list_route_profiles,
+            apply_route_profile,
+            get_default_gateway,
+            get_allowed_commands,
+            get_ip_configuration,
+            run_network_diagnostics,
+            cancel_fping_scan,
+            get_route_audit_log,
+            get_runtime_capabilities,
+            get_environment_report,
+            verify_system_commands,
+            repair_network_stack,
+            check_connectivity,
+            start_gateway_monitor,
+            stop_gateway_monitor,
+            measure_service_latency,
+            test_connection,
+            renew_dhcp_lease,
+            remove_bloatware_category,
+            reinstall_appx_package,
+            get_scheduled_tasks,
+            set_scheduled_task_state,
+            get_services,
+            set_service_state,
+            get_startup_items,
+            set_startup_item_enabled,
+            reverse_dns,
+            discover_lan_devices,
+            lookup_mac_vendor,
+            get_dns_cache,
+            flush_dns_cache,
+            monitor_connection_quality,
+            stop_connection_quality_monitor,
+            set_route_metric,
+            find_route_for_destination,
+            export_interfaces,
+            generate_diagnostics_bundle,
+            get_primary_interface,
+            delete_routes_to,
+            flush_temporary_routes,
+            find_optimal_mtu,
+            get_ipv6_neighbors,
+            get_firewall_profiles,
+            set_firewall_profile,
+            benchmark_dns,
+            get_power_plans,
+            set_active_power_plan,
+            get_battery_status,
+            ping_detailed,
+            clear_network_caches,
+            detect_ip_conflicts,
+            backup_network_config,
+            restore_network_config,
+            trace_path_to,
+            get_interface_addresses,
+            is_port_open,
+            get_persistent_routes,
+            set_ipv6_enabled,
+            reset_interface,
+            detect_captive_portal,
+            get_windows_version,
+            get_processes,
+            kill_process,
+            get_system_uptime,
+            get_disk_usage,
+            get_cache_target_details,
+            compute_subnet_info,
+            get_preferences,
+            set_preferences,
+            preview_default_gateway_change,
+            get_default_gateways,
+            flush_arp_cache,
+            get_process_network_usage,
+            add_temporary_route,
+            cancel_temporary_route,
+            resolve_host,
+            audit_routes,
+            monitor_dns,
+            stop_dns_monitor,
+            get_network_event_log,
+            test_path_mtu,
+            get_effective_dns,
+            run_network_commands,
+            cancel_cache_cleanup,
+            export_bloatware_report,
+            can_perform,
+            snapshot_routes,
+            diff_routes,
+            ping_percentiles,
+            set_doh,
+            check_stack_health,
+            get_interface_metric,
+            set_interface_metric,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while running tauri application")
+        .run(|_, event| {
+            if let tauri::RunEvent::Exit = event {
+                network::remove_temporary_routes();
+            }
+        });
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct EnvironmentReport {
+    pub windows_build: Option<u32>,
+    pub is_admin: Option<bool>,
+    pub has_webview2: bool,
+    pub missing_commands: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct CommandAvailability {
+    pub command: String,
+    pub available: bool,
+    pub path: Option<String>,
+}
+
+/// Re-run the startup environment checks so the UI can show them in-app
+#[tauri::command]
+async fn get_environment_report() -> Result<EnvironmentReport, NetworkError> {
+    Ok(collect_environment_report())
+}
+
+#[cfg(target_os = "windows")]
+fn collect_environment_report() -> EnvironmentReport {
+    EnvironmentReport {
+        windows_build: detect_windows_build_number(),
+        is_admin: is_running_as_admin(),
+        has_webview2: has_webview2_runtime(),
+        missing_commands: REQUIRED_COMMANDS
+            .iter()
+            .filter(|command| !command_exists(command))
+            .map(|command| (*command).to_string())
+            .collect(),
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn collect_environment_report() -> EnvironmentReport {
+    EnvironmentReport {
+        windows_build: None,
+        is_admin: None,
+        has_webview2: false,
+        missing_commands: Vec::new(),
+    }
+}
+
+/// Every entry of `REQUIRED_COMMANDS` with the path `where` resolves it to
+#[tauri::command]
+async fn verify_system_commands() -> Result<Vec<CommandAvailability>, NetworkError> {
+    Ok(collect_command_availability())
 }
 
 #[cfg(target_os = "windows")]
-fn validate_runtime_environment() -> Result<(), String> {
+fn collect_command_availability() -> Vec<CommandAvailability> {
+    REQUIRED_COMMANDS
+        .iter()
+        .map(|command| {
+            let path = run_hidden("where", &[command])
+                .filter(|output| output.status.success())
+                .and_then(|output| {
+                    String::from_utf8_lossy(&output.stdout)
+                        .lines()
+                        .map(str::trim)
+                        .find(|line| !line.is_empty())
+                        .map(str::to_string)
+                });
+            CommandAvailability {
+                command: (*command).to_string(),
+                available: path.is_some(),
+                path,
+            }
+        })
+        .collect()
+}
+
+#[cfg(not(target_os = "windows"))]
+fn collect_command_availability() -> Vec<CommandAvailability> {
+    Vec::new()
+}
+
+/// Returns `Ok(true)` when everything passes except Administrator privileges:
+/// the app then starts in degraded (read-only) mode instead of refusing to launch.
+#[cfg(target_os = "windows")]
+fn validate_runtime_environment() -> Result<bool, String> {
+    let report = collect_environment_report();
+    let min_build = min_windows_build();
     let mut failures: Vec<String> = Vec::new();
+    let mut admin_failure: Option<String> = None;
 
-    match detect_windows_build_number() {
-        Some(build) if build >= MIN_WINDOWS_BUILD => {}
+    match report.windows_build {
+        Some(build) if build >= min_build => {}
         Some(build) => failures.push(format!(
-            "Windows build {build} detected. This app supports Windows 10/11 (build >= {MIN_WINDOWS_BUILD})."
+            "Windows build {build} detected. This app supports Windows 10/11 (build >= {min_build})."
         )),
         None => failures.push("Unable to detect Windows build number.".to_string()),
     }
 
-    match is_running_as_admin() {
+    match report.is_admin {
         Some(true) => {}
-        Some(false) => failures.push(
-            "The app must run with Administrator privileges to manage routes and NIC settings."
-                .to_string(),
-        ),
-        None => failures.push("Unable to verify Administrator privileges.".to_string()),
+        Some(false) => {
+            admin_failure = Some(
+                "The app must run with Administrator privileges to manage routes and NIC settings."
+                    .to_string(),
+            )
+        }
+        None => admin_failure = Some("Unable to verify Administrator privileges.".to_string()),
     }
 
-    if !has_webview2_runtime() {
+    if !report.has_webview2 {
         failures.push("Microsoft Edge WebView2 Runtime is not installed.".to_string());
     }
 
-    for command in REQUIRED_COMMANDS {
-        if !command_exists(command) {
-            failures.push(format!("Required system command is missing: {command}"));
-        }
+    for command in &report.missing_commands {
+        failures.push(format!("Required system command is missing: {command}"));
     }
 
     if failures.is_empty() {
-        Ok(())
+        if let Some(reason) = admin_failure {
+            eprintln!("{reason} Starting in degraded (read-only) mode.");
+            return Ok(true);
+        }
+        Ok(false)
     } else {
+        failures.extend(admin_failure);
         let bullet_list = failures
             .iter()
             .map(|item| format!("- {item}"))
@@ -97,7 +322,7 @@ fn validate_runtime_environment() -> Result<(), String> {
 }
 
 #[cfg(not(target_os = "windows"))]
-fn validate_runtime_environment() -> Result<(), String> {
+fn validate_runtime_environment() -> Result<bool, String> {
     Err("This build only supports Windows.".to_string())
 }
 
@@ -129,6 +354,31 @@ fn env_flag_enabled(var_name: &str) -> bool {
         .unwrap_or(false)
 }
 
+#[cfg(target_os = "windows")]
+fn min_windows_build() -> u32 {
+    match std::env::var(MIN_WINDOWS_BUILD_ENV) {
+        Ok(value) => match value.trim().parse::<u32>() {
+            Ok(build) => {
+                eprintln!(
+                    "[DEV] {} override in effect: minimum Windows build {} (default {}).",
+                    MIN_WINDOWS_BUILD_ENV, build, MIN_WINDOWS_BUILD
+                );
+                build
+            }
+            Err(_) => {
+                eprintln!(
+                    "[DEV] Ignoring invalid {} value '{}', using default {}.",
+                    MIN_WINDOWS_BUILD_ENV,
+                    value.trim(),
+                    MIN_WINDOWS_BUILD
+                );
+                MIN_WINDOWS_BUILD
+            }
+        },
+        Err(_) => MIN_WINDOWS_BUILD,
+    }
+}
+
 #[cfg(target_os = "windows")]
 fn run_hidden(program: &str, args: &[&str]) -> Option<std::process::Output> {
     Command::new(program)
@@ -139,7 +389,7 @@ fn run_hidden(program: &str, args: &[&str]) -> Option<std::process::Output> {
 }
 
 #[cfg(target_os = "windows")]
-fn detect_windows_build_number() -> Option<u32> {
+pub(crate) fn detect_windows_build_number() -> Option<u32> {
     let output = run_hidden(
         "powershell",
         &[
@@ -161,7 +411,7 @@ fn detect_windows_build_number() -> Option<u32> {
 }
 
 #[cfg(target_os = "windows")]
-fn is_running_as_admin() -> Option<bool> {
+pub(crate) fn is_running_as_admin() -> Option<bool> {
     let output = run_hidden(
         "powershell",
         &[
@@ -181,7 +431,7 @@ fn is_running_as_admin() -> Option<bool> {
 }
 
 #[cfg(target_os = "windows")]
-fn command_exists(name: &str) -> bool {
+pub(crate) fn command_exists(name: &str) -> bool {
     run_hidden("where", &[name])
         .map(|output| output.status.success())
         .unwrap_or(false)
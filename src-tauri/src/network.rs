@@ -1,63 +1,539 @@
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::fs;
-use std::net::TcpStream;
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
 use std::os::windows::process::CommandExt;
-use std::process::Command;
-use std::sync::{Arc, Mutex};
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::{Arc, LazyLock, Mutex};
 use std::thread;
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tauri::{Emitter, Manager};
 
 const CREATE_NO_WINDOW: u32 = 0x08000000;
-const BLOATWARE_CANDIDATES: [(&str, &str); 29] = [
-    ("Clipchamp.Clipchamp", "Clipchamp"),
-    ("Microsoft.BingNews", "Microsoft News"),
-    ("Microsoft.BingWeather", "Microsoft Weather"),
-    ("Microsoft.GetHelp", "Get Help"),
-    ("Microsoft.Getstarted", "Get Started"),
-    ("Microsoft.GamingApp", "Xbox"),
-    ("Microsoft.Microsoft3DViewer", "3D Viewer"),
-    (
-        "Microsoft.MicrosoftOfficeHub",
-        "Microsoft 365 (Office Hub)",
-    ),
+// Upper bound for a single system command so a stalled child can't hang an invoke
+const DEFAULT_POWERSHELL_TIMEOUT: Duration = Duration::from_secs(120);
+const DEFAULT_CMD_TIMEOUT: Duration = Duration::from_secs(60);
+// Force UTF-8 (without BOM) for everything PowerShell writes to the pipe, so
+// non-English locales don't come back as codepage-mangled text.
+const PS_UTF8_PREAMBLE: &str = "$__srpUtf8 = New-Object System.Text.UTF8Encoding $false; [Console]::OutputEncoding = $__srpUtf8; $OutputEncoding = $__srpUtf8;";
+const INTERNET_SETTINGS_KEY: &str =
+    r"HKCU:\Software\Microsoft\Windows\CurrentVersion\Internet Settings";
+const DEFAULT_SPEED_TEST_URL: &str = "http://speedtest.tele2.net/10MB.zip";
+const SPEED_TEST_MAX_SECS: u64 = 15;
+// Whitelist of allowed commands for security (used by run_network_command)
+const ALLOWED_COMMAND_PREFIXES: [&str; 13] = [
+    "ipconfig",
+    "ipconfig /displaydns",
+    "powercfg /batteryreport",
+    "tracert",
+    "nslookup",
+    "netsh wlan show interface",
+    "netsh winhttp reset proxy",
+    "netsh int ip reset",
+    "netsh winsock reset",
+    "netsh interface ip delete arpcache",
+    "netsh advfirewall reset",
+    "powershell -noprofile -command get-netadapter",
+    "powershell -noprofile -command test-netconnection",
+];
+const INTERNET_PROBE_ADDR: &str = "8.8.8.8:53";
+const INTERNET_PROBE_ADDR_V6: &str = "[2001:4860:4860::8888]:53";
+const PUBLIC_PING_HOST: &str = "8.8.8.8";
+const DNS_TEST_HOST: &str = "www.microsoft.com";
+// IPv4 + ICMP header bytes on top of a ping payload
+const ICMP_OVERHEAD: u32 = 28;
+// Common link/tunnel MTUs (Ethernet, PPPoE, VPNs, IPv6 minimum, IPv4 minimum), largest first
+const MTU_LADDER: [u32; 12] = [
+    1500, 1492, 1480, 1460, 1440, 1420, 1400, 1380, 1360, 1280, 1024, 576,
+];
+// DoH templates for well-known resolvers, used when Windows has none registered for the server
+const DOH_TEMPLATES: [(&str, &str); 6] = [
+    ("1.1.1.1", "https://cloudflare-dns.com/dns-query"),
+    ("1.0.0.1", "https://cloudflare-dns.com/dns-query"),
+    ("8.8.8.8", "https://dns.google/dns-query"),
+    ("8.8.4.4", "https://dns.google/dns-query"),
+    ("9.9.9.9", "https://dns.quad9.net/dns-query"),
+    ("149.112.112.112", "https://dns.quad9.net/dns-query"),
+];
+// Windows' own NCSI probe: anything other than this exact body means the request was intercepted
+const CAPTIVE_PORTAL_PROBE_URL: &str = "http://www.msftconnecttest.com/connecttest.txt";
+const CAPTIVE_PORTAL_EXPECTED_BODY: &str = "Microsoft Connect Test";
+// Set at startup when the app runs without Administrator privileges
+static DEGRADED_MODE: AtomicBool = AtomicBool::new(false);
+
+// Features gated on Administrator privileges, as (feature id, requires admin)
+const RUNTIME_FEATURES: [(&str, bool); 14] = [
+    ("interface_list", false),
+    ("routing_table", false),
+    ("ping", false),
+    ("fping_scan", false),
+    ("diagnostics", false),
+    ("speed_test", false),
+    ("proxy_settings", false),
+    ("cache_cleanup", true),
+    ("route_change", true),
+    ("route_profiles", true),
+    ("firewall_rules", true),
+    ("bloatware_removal", true),
+    ("network_repair", true),
+    ("nic_config", true),
+];
+
+// Stop flag of the running gateway monitor, if any
+static GATEWAY_MONITOR: Mutex<Option<Arc<AtomicBool>>> = Mutex::new(None);
+// Stop flags of the running connection-quality and DNS monitors, if any
+static QUALITY_MONITOR: Mutex<Option<Arc<AtomicBool>>> = Mutex::new(None);
+static DNS_MONITOR: Mutex<Option<Arc<AtomicBool>>> = Mutex::new(None);
+
+// Set by `cancel_cache_cleanup`; checked between targets in `clear_cache_targets`
+static CACHE_CLEANUP_CANCEL: AtomicBool = AtomicBool::new(false);
+
+// Cancellation flags for in-flight fping scans, keyed by caller-provided scan id
+static FPING_SCANS: LazyLock<Mutex<HashMap<String, Arc<AtomicBool>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+// Auto-expiring routes from add_temporary_route, keyed by route id, with their cancel flags
+type TempRouteEntry = (TemporaryRoute, Arc<AtomicBool>);
+static TEMP_ROUTES: LazyLock<Mutex<HashMap<String, TempRouteEntry>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+// Routing table captured by snapshot_routes, compared against by diff_routes
+static ROUTE_SNAPSHOT: Mutex<Option<Vec<RouteEntry>>> = Mutex::new(None);
+
+// Last check_internet probe, reused for INTERNET_CHECK_TTL to absorb UI polling bursts
+static INTERNET_CHECK_CACHE: Mutex<Option<(Instant, bool)>> = Mutex::new(None);
+const INTERNET_CHECK_TTL: Duration = Duration::from_secs(2);
+
+const ROUTE_AUDIT_LOG_FILE: &str = "route_audit.log";
+const PREFERENCES_FILE: &str = "preferences.json";
+const NETWORK_EVENT_PROVIDERS: [&str; 5] = [
+    "Tcpip",
+    "Microsoft-Windows-Tcpip",
+    "Microsoft-Windows-Dhcp-Client",
+    "Microsoft-Windows-DHCPv6-Client",
+    "Microsoft-Windows-DNS-Client",
+];
+// Curated endpoints for measure_service_latency, as (name, endpoint)
+const SERVICE_ENDPOINTS: [(&str, &str); 8] = [
+    ("Google DNS", "8.8.8.8"),
+    ("Cloudflare DNS", "1.1.1.1"),
+    ("Quad9 DNS", "9.9.9.9"),
+    ("OpenDNS", "208.67.222.222"),
+    ("Google", "www.google.com"),
+    ("Microsoft", "www.microsoft.com"),
+    ("AWS US East (N. Virginia)", "dynamodb.us-east-1.amazonaws.com"),
+    ("AWS Asia Pacific (Singapore)", "dynamodb.ap-southeast-1.amazonaws.com"),
+];
+// Cache recipes that stop/start services and may need a retry
+const SERVICE_CACHE_TARGETS: [&str; 1] = ["windows_update_cache"];
+const BLOATWARE_CANDIDATES: [(&str, &str, &str); 29] = [
+    ("Clipchamp.Clipchamp", "Clipchamp", "Media"),
+    ("Microsoft.BingNews", "Microsoft News", "Media"),
+    ("Microsoft.BingWeather", "Microsoft Weather", "System"),
+    ("Microsoft.GetHelp", "Get Help", "System"),
+    ("Microsoft.Getstarted", "Get Started", "System"),
+    ("Microsoft.GamingApp", "Xbox", "Gaming"),
+    ("Microsoft.Microsoft3DViewer", "3D Viewer", "Media"),
+    ("Microsoft.MicrosoftOfficeHub", "Microsoft 365 (Office Hub)", "Office"),
+    ("Microsoft.MicrosoftSolitaireCollection", "Microsoft Solitaire Collection", "Gaming"),
+    ("Microsoft.MixedReality.Portal", "Mixed Reality Portal", "System"),
+    ("Microsoft.OutlookForWindows", "Outlook for Windows", "Communication"),
+    ("Microsoft.People", "People", "Communication"),
+    ("Microsoft.PowerAutomateDesktop", "Power Automate", "Office"),
+    ("Microsoft.SkypeApp", "Skype", "Communication"),
+    ("Microsoft.Todos", "Microsoft To Do", "Office"),
+    ("Microsoft.WindowsAlarms", "Clock", "System"),
+    ("microsoft.windowscommunicationsapps", "Mail and Calendar", "Communication"),
+    ("Microsoft.WindowsFeedbackHub", "Feedback Hub", "System"),
+    ("Microsoft.WindowsMaps", "Maps", "System"),
+    ("Microsoft.Xbox.TCUI", "Xbox TCUI", "Gaming"),
+    ("Microsoft.XboxGameOverlay", "Xbox Game Bar Plugin", "Gaming"),
+    ("Microsoft.XboxGamingOverlay", "Xbox Game Bar", "Gaming"),
+    ("Microsoft.XboxIdentityProvider", "Xbox Identity Provider", "Gaming"),
+    ("Microsoft.XboxSpeechToTextOverlay", "Xbox Speech To Text", "Gaming"),
+    ("Microsoft.YourPhone", "Phone Link", "Communication"),
+    ("Microsoft.ZuneMusic", "Media Player (Legacy Music)", "Media"),
+    ("Microsoft.ZuneVideo", "Movies & TV", "Media"),
+    ("MicrosoftTeams", "Microsoft Teams", "Communication"),
+    ("MicrosoftCorporationII.MicrosoftFamily", "Microsoft Family", "System"),
+];
+// Preinstalled OEM utilities: (package name, label, vendor). Shown under the "OEM" category.
+const OEM_BLOATWARE: [(&str, &str, &str); 13] = [
+    ("DellInc.DellSupportAssistforPCs", "Dell SupportAssist", "Dell"),
+    ("DellInc.DellDigitalDelivery", "Dell Digital Delivery", "Dell"),
+    ("DellInc.DellCustomerConnect", "Dell Customer Connect", "Dell"),
+    ("DellInc.PartnerPromo", "Dell Partner Promo", "Dell"),
+    ("AD2F1837.HPJumpStarts", "HP JumpStarts", "HP"),
+    ("AD2F1837.HPSupportAssistant", "HP Support Assistant", "HP"),
+    ("AD2F1837.HPPrivacySettings", "HP Privacy Settings", "HP"),
+    ("AD2F1837.myHP", "myHP", "HP"),
+    ("E046963F.LenovoCompanion", "Lenovo Vantage", "Lenovo"),
+    ("E0469640.LenovoUtility", "Lenovo Utility", "Lenovo"),
+    ("LenovoCorporation.LenovoID", "Lenovo ID", "Lenovo"),
+    ("B9ECED6F.ASUSPCAssistant", "MyASUS", "ASUS"),
+    ("B9ECED6F.ASUSGiftBox", "ASUS GiftBox", "ASUS"),
+];
+// Telemetry/maintenance tasks that are safe to toggle: (task path, task name)
+const SAFE_SCHEDULED_TASKS: [(&str, &str); 12] = [
+    (r"\Microsoft\Windows\Application Experience\", "Microsoft Compatibility Appraiser"),
+    (r"\Microsoft\Windows\Application Experience\", "ProgramDataUpdater"),
+    (r"\Microsoft\Windows\Autochk\", "Proxy"),
+    (r"\Microsoft\Windows\Customer Experience Improvement Program\", "Consolidator"),
+    (r"\Microsoft\Windows\Customer Experience Improvement Program\", "UsbCeip"),
     (
-        "Microsoft.MicrosoftSolitaireCollection",
-        "Microsoft Solitaire Collection",
+        r"\Microsoft\Windows\DiskDiagnostic\",
+        "Microsoft-Windows-DiskDiagnosticDataCollector",
     ),
-    ("Microsoft.MixedReality.Portal", "Mixed Reality Portal"),
-    ("Microsoft.OutlookForWindows", "Outlook for Windows"),
-    ("Microsoft.People", "People"),
-    ("Microsoft.PowerAutomateDesktop", "Power Automate"),
-    ("Microsoft.SkypeApp", "Skype"),
-    ("Microsoft.Todos", "Microsoft To Do"),
-    ("Microsoft.WindowsAlarms", "Clock"),
-    ("microsoft.windowscommunicationsapps", "Mail and Calendar"),
-    ("Microsoft.WindowsFeedbackHub", "Feedback Hub"),
-    ("Microsoft.WindowsMaps", "Maps"),
-    ("Microsoft.Xbox.TCUI", "Xbox TCUI"),
-    ("Microsoft.XboxGameOverlay", "Xbox Game Bar Plugin"),
-    ("Microsoft.XboxGamingOverlay", "Xbox Game Bar"),
-    ("Microsoft.XboxIdentityProvider", "Xbox Identity Provider"),
-    ("Microsoft.XboxSpeechToTextOverlay", "Xbox Speech To Text"),
-    ("Microsoft.YourPhone", "Phone Link"),
-    ("Microsoft.ZuneMusic", "Media Player (Legacy Music)"),
-    ("Microsoft.ZuneVideo", "Movies & TV"),
-    ("MicrosoftTeams", "Microsoft Teams"),
-    ("MicrosoftCorporationII.MicrosoftFamily", "Microsoft Family"),
+    (r"\Microsoft\Windows\Feedback\Siuf\", "DmClient"),
+    (r"\Microsoft\Windows\Feedback\Siuf\", "DmClientOnScenarioDownload"),
+    (r"\Microsoft\Windows\Maps\", "MapsToastTask"),
+    (r"\Microsoft\Windows\Maps\", "MapsUpdateTask"),
+    (r"\Microsoft\XblGameSave\", "XblGameSaveTask"),
+    (r"\Microsoft\Office\", "OfficeTelemetryAgentLogOn"),
+];
+// Non-critical services that may be started/stopped/restarted from the UI
+const CONTROLLABLE_SERVICES: [&str; 14] = [
+    "wuauserv",
+    "bits",
+    "DoSvc",
+    "DiagTrack",
+    "dmwappushservice",
+    "SysMain",
+    "WSearch",
+    "Spooler",
+    "Fax",
+    "MapsBroker",
+    "RetailDemo",
+    "XblAuthManager",
+    "XblGameSave",
+    "XboxNetApiSvc",
+];
+const STARTUP_APPROVED_KEY: &str =
+    r"Software\Microsoft\Windows\CurrentVersion\Explorer\StartupApproved";
+// Startup locations: (location id, registry hive, StartupApproved subkey)
+const STARTUP_LOCATIONS: [(&str, &str, &str); 4] = [
+    (r"HKCU\Run", "HKCU", "Run"),
+    (r"HKLM\Run", "HKLM", "Run"),
+    (r"HKCU\StartupFolder", "HKCU", "StartupFolder"),
+    (r"HKLM\StartupFolder", "HKLM", "StartupFolder"),
+];
+// MAC OUI prefixes (first 3 octets, uppercase hex) for common LAN vendors
+const OUI_VENDORS: &[(&str, &str)] = &[
+    ("00000C", "Cisco"),
+    ("0000F0", "Samsung"),
+    ("000393", "Apple"),
+    ("000569", "VMware"),
+    ("00095B", "Netgear"),
+    ("0009BF", "Nintendo"),
+    ("000C29", "VMware"),
+    ("000E58", "Sonos"),
+    ("001132", "Synology"),
+    ("00155D", "Microsoft (Hyper-V)"),
+    ("0017F2", "Apple"),
+    ("001A11", "Google"),
+    ("001B63", "Apple"),
+    ("001CB3", "Apple"),
+    ("001E58", "D-Link"),
+    ("00248C", "ASUSTek"),
+    ("002719", "TP-Link"),
+    ("0050F2", "Microsoft"),
+    ("005056", "VMware"),
+    ("00AA00", "Intel"),
+    ("00E04C", "Realtek"),
+    ("0418D6", "Ubiquiti"),
+    ("080027", "VirtualBox"),
+    ("240AC4", "Espressif"),
+    ("24A43C", "Ubiquiti"),
+    ("525400", "QEMU/KVM"),
+    ("5CCF7F", "Espressif"),
+    ("802AA8", "Ubiquiti"),
+    ("B827EB", "Raspberry Pi"),
+    ("DCA632", "Raspberry Pi"),
+    ("E45F01", "Raspberry Pi"),
+    ("F0272D", "Amazon"),
+];
+
+const CACHE_RECIPES: [CacheRecipe; 14] = [
+    CacheRecipe {
+        id: "user_temp",
+        label: "User Temp",
+        description: "Clear %LOCALAPPDATA%\\Temp",
+        paths: &[("LOCALAPPDATA", "Temp\\*")],
+        before: "",
+        after: "",
+    },
+    CacheRecipe {
+        id: "windows_temp",
+        label: "Windows Temp",
+        description: "Clear Windows temporary files",
+        paths: &[("WINDIR", "Temp\\*")],
+        before: "",
+        after: "",
+    },
+    CacheRecipe {
+        id: "windows_update_cache",
+        label: "Windows Update Cache",
+        description: "Clear SoftwareDistribution download cache",
+        paths: &[("WINDIR", "SoftwareDistribution\\Download\\*")],
+        before: r#"
+try {
+  Stop-Service -Name wuauserv -Force -ErrorAction Stop
+  Stop-Service -Name bits -Force -ErrorAction Stop
+} catch {
+  Start-Service -Name wuauserv -ErrorAction SilentlyContinue
+  Start-Service -Name bits -ErrorAction SilentlyContinue
+  [Console]::Error.WriteLine("Service stop failed: $($_.Exception.Message)")
+  exit 1
+}
+"#,
+        after: r#"
+Start-Service -Name wuauserv -ErrorAction SilentlyContinue
+Start-Service -Name bits -ErrorAction SilentlyContinue
+"#,
+    },
+    CacheRecipe {
+        id: "prefetch",
+        label: "Prefetch",
+        description: "Clear prefetch cache files",
+        paths: &[("WINDIR", "Prefetch\\*")],
+        before: "",
+        after: "",
+    },
+    CacheRecipe {
+        id: "explorer_cache",
+        label: "Explorer Cache (thumbnail/icon)",
+        description: "Clear icon and thumbnail cache",
+        paths: &[
+            ("LOCALAPPDATA", "Microsoft\\Windows\\Explorer\\thumbcache_*.db"),
+            ("LOCALAPPDATA", "Microsoft\\Windows\\Explorer\\iconcache_*.db"),
+        ],
+        before: "",
+        after: r#"
+Start-Process -FilePath ie4uinit.exe -ArgumentList '-ClearIconCache' -NoNewWindow -Wait -ErrorAction SilentlyContinue
+"#,
+    },
+    CacheRecipe {
+        id: "edge_cache",
+        label: "Microsoft Edge Cache",
+        description: "Clear Edge browser cache",
+        paths: &[
+            ("LOCALAPPDATA", "Microsoft\\Edge\\User Data\\Default\\Cache\\*"),
+            ("LOCALAPPDATA", "Microsoft\\Edge\\User Data\\Default\\Code Cache\\*"),
+            ("LOCALAPPDATA", "Microsoft\\Edge\\User Data\\Default\\GPUCache\\*"),
+        ],
+        before: "",
+        after: "",
+    },
+    CacheRecipe {
+        id: "chrome_cache",
+        label: "Google Chrome Cache",
+        description: "Clear Chrome browser cache",
+        paths: &[
+            ("LOCALAPPDATA", "Google\\Chrome\\User Data\\Default\\Cache\\*"),
+            ("LOCALAPPDATA", "Google\\Chrome\\User Data\\Default\\Code Cache\\*"),
+            ("LOCALAPPDATA", "Google\\Chrome\\User Data\\Default\\GPUCache\\*"),
+        ],
+        before: "",
+        after: "",
+    },
+    CacheRecipe {
+        id: "firefox_cache",
+        label: "Mozilla Firefox Cache",
+        description: "Clear Firefox browser cache",
+        paths: &[("LOCALAPPDATA", "Mozilla\\Firefox\\Profiles\\*\\cache2\\*")],
+        before: "",
+        after: "",
+    },
+    CacheRecipe {
+        id: "inet_cache",
+        label: "INetCache",
+        description: "Clear legacy internet cache",
+        paths: &[("LOCALAPPDATA", "Microsoft\\Windows\\INetCache\\*")],
+        before: "",
+        after: "",
+    },
+    CacheRecipe {
+        id: "web_cache",
+        label: "WebCache",
+        description: "Clear Windows WebCache store",
+        paths: &[("LOCALAPPDATA", "Microsoft\\Windows\\WebCache\\*")],
+        before: "",
+        after: "",
+    },
+    CacheRecipe {
+        id: "crash_dumps",
+        label: "Crash Dumps",
+        description: "Clear local crash dump files",
+        paths: &[("LOCALAPPDATA", "CrashDumps\\*")],
+        before: "",
+        after: "",
+    },
+    CacheRecipe {
+        id: "wer_reports",
+        label: "Windows Error Reporting (WER)",
+        description: "Clear WER reports and queue",
+        paths: &[
+            ("ProgramData", "Microsoft\\Windows\\WER\\*"),
+            ("LOCALAPPDATA", "Microsoft\\Windows\\WER\\*"),
+        ],
+        before: "",
+        after: "",
+    },
+    CacheRecipe {
+        id: "d3d_shader_cache",
+        label: "DirectX Shader Cache (D3DSCache)",
+        description: "Clear D3DSCache",
+        paths: &[("LOCALAPPDATA", "D3DSCache\\*")],
+        before: "",
+        after: "",
+    },
+    CacheRecipe {
+        id: "store_cache",
+        label: "Microsoft Store Cache",
+        description: "Run wsreset and clear the Store package cache",
+        paths: &[(
+            "LOCALAPPDATA",
+            "Packages\\Microsoft.WindowsStore_8wekyb3d8bbwe\\LocalCache\\*",
+        )],
+        before: r#"
+$wsreset = Start-Process -FilePath wsreset.exe -WindowStyle Hidden -Wait -PassThru -ErrorAction SilentlyContinue
+if (-not $wsreset) {
+  Write-Output '[FAIL] wsreset.exe could not be started.'
+  exit 0
+}
+"#,
+        after: "",
+    },
 ];
 
 // ======================== DATA TYPES ========================
 
+/// Error returned by every command. Serialized as `{ "kind": "...", "message": "...", ...fields }`
+/// so the UI can match on `kind` and still show `message` as-is.
+#[derive(Debug, Clone)]
+pub enum NetworkError {
+    NotAdmin { operation: String },
+    CommandFailed { command: String, stderr: String },
+    ParseError { message: String },
+    InvalidInput { message: String },
+    Timeout { command: String, timeout_secs: u64 },
+    NotFound { message: String },
+    Io { message: String },
+}
+
+impl NetworkError {
+    fn invalid(message: impl Into<String>) -> Self {
+        NetworkError::InvalidInput {
+            message: message.into(),
+        }
+    }
+
+    fn parse(message: impl Into<String>) -> Self {
+        NetworkError::ParseError {
+            message: message.into(),
+        }
+    }
+
+    fn not_found(message: impl Into<String>) -> Self {
+        NetworkError::NotFound {
+            message: message.into(),
+        }
+    }
+
+    fn io(message: impl Into<String>) -> Self {
+        NetworkError::Io {
+            message: message.into(),
+        }
+    }
+
+    fn command_failed(command: impl Into<String>, stderr: impl Into<String>) -> Self {
+        NetworkError::CommandFailed {
+            command: command.into(),
+            stderr: stderr.into(),
+        }
+    }
+
+    fn kind(&self) -> &'static str {
+        match self {
+            NetworkError::NotAdmin { .. } => "not_admin",
+            NetworkError::CommandFailed { .. } => "command_failed",
+            NetworkError::ParseError { .. } => "parse_error",
+            NetworkError::InvalidInput { .. } => "invalid_input",
+            NetworkError::Timeout { .. } => "timeout",
+            NetworkError::NotFound { .. } => "not_found",
+            NetworkError::Io { .. } => "io",
+        }
+    }
+}
+
+impl std::fmt::Display for NetworkError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NetworkError::NotAdmin { operation } => write!(
+                f,
+                "{} requires Administrator privileges. Restart Super Route Pro as Administrator.",
+                operation
+            ),
+            NetworkError::CommandFailed { command, stderr } => {
+                if stderr.trim().is_empty() {
+                    write!(f, "{} failed", command)
+                } else {
+                    write!(f, "{}", stderr)
+                }
+            }
+            NetworkError::Timeout {
+                command,
+                timeout_secs,
+            } => write!(f, "{} command timed out after {}s", command, timeout_secs),
+            NetworkError::ParseError { message }
+            | NetworkError::InvalidInput { message }
+            | NetworkError::NotFound { message }
+            | NetworkError::Io { message } => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for NetworkError {}
+
+impl Serialize for NetworkError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("kind", self.kind())?;
+        map.serialize_entry("message", &self.to_string())?;
+        match self {
+            NetworkError::NotAdmin { operation } => map.serialize_entry("operation", operation)?,
+            NetworkError::CommandFailed { command, stderr } => {
+                map.serialize_entry("command", command)?;
+                map.serialize_entry("stderr", stderr)?;
+            }
+            NetworkError::Timeout {
+                command,
+                timeout_secs,
+            } => {
+                map.serialize_entry("command", command)?;
+                map.serialize_entry("timeout_secs", timeout_secs)?;
+            }
+            _ => {}
+        }
+        map.end()
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct NetworkInterface {
     pub index: String,
     pub ip: String,
     pub gateway: String,
     pub description: String,
+    pub gateway_latency_ms: Option<u32>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct RouteEntry {
     pub destination: String,
     pub netmask: String,
@@ -79,6 +555,9 @@ pub struct FpingHostResult {
     pub success: bool,
     pub latency_ms: u32,
     pub output: String,
+    pub sent: u32,
+    pub received: u32,
+    pub loss_percent: f32,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -90,6 +569,12 @@ pub struct FpingScanResult {
     pub avg_ms: u32,
     pub max_ms: u32,
     pub hosts: Vec<FpingHostResult>,
+    pub cancelled: bool,
+    /// The scan ended because `stop_after` live hosts had answered.
+    pub stopped_early: bool,
+    /// Ping workers used. More workers finish large sweeps faster but burst more ICMP
+    /// at once, which slow or metered links (and some IDS setups) handle poorly.
+    pub workers: u32,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -102,6 +587,8 @@ pub struct CommandResult {
 pub struct BloatwareItem {
     pub package_name: String,
     pub label: String,
+    pub category: String,
+    pub vendor: Option<String>,
     pub installed: bool,
 }
 
@@ -110,288 +597,2447 @@ pub struct BatteryReportResult {
     pub html: String,
 }
 
-// ======================== HELPERS ========================
-
-fn run_powershell(script: &str) -> Result<String, String> {
-    let output = Command::new("powershell")
-        .args(["-NoProfile", "-NonInteractive", "-Command", script])
-        .creation_flags(CREATE_NO_WINDOW)
-        .output()
-        .map_err(|e| format!("Failed to run PowerShell: {}", e))?;
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProxySettings {
+    pub enabled: bool,
+    pub server: String,
+    pub bypass_list: Vec<String>,
+}
 
-    if output.status.success() {
-        Ok(String::from_utf8_lossy(&output.stdout).to_string())
-    } else {
-        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
-        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-        // Some commands write to stdout even on "failure"
-        if !stdout.is_empty() {
-            Ok(stdout)
-        } else {
-            Err(stderr)
-        }
-    }
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SpeedTestResult {
+    pub download_mbps: f32,
+    pub bytes: u64,
+    pub seconds: f32,
 }
 
-fn run_cmd(program: &str, args: &[&str]) -> Result<String, String> {
-    let output = Command::new(program)
-        .args(args)
-        .creation_flags(CREATE_NO_WINDOW)
-        .output()
-        .map_err(|e| format!("Failed to run {}: {}", program, e))?;
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RouteSpec {
+    pub destination: String,
+    pub mask: String,
+    pub gateway: String,
+    pub metric: String,
+    pub interface_index: Option<String>,
+}
 
-    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DefaultGateway {
+    pub gateway: String,
+    pub interface_index: String,
+    pub interface_alias: String,
+    /// Effective metric (route metric + interface metric), the value Windows ranks routes by
+    pub metric: u32,
+}
 
-    if output.status.success() {
-        Ok(stdout)
-    } else if !stdout.is_empty() {
-        Ok(format!("{}\n{}", stdout, stderr))
-    } else {
-        Err(stderr)
-    }
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct AdapterConfig {
+    pub adapter_name: String,
+    pub description: String,
+    pub mac: String,
+    pub ipv4: Vec<String>,
+    pub ipv6: Vec<String>,
+    pub subnet_mask: String,
+    pub default_gateway: Vec<String>,
+    pub dns_servers: Vec<String>,
+    pub dhcp_server: String,
 }
 
-fn prefix_to_mask(prefix: u32) -> String {
-    if prefix > 32 {
-        return "255.255.255.255".to_string();
-    }
-    let mask: u32 = if prefix == 0 {
-        0
-    } else {
-        0xFFFFFFFF << (32 - prefix)
-    };
-    format!(
-        "{}.{}.{}.{}",
-        (mask >> 24) & 0xFF,
-        (mask >> 16) & 0xFF,
-        (mask >> 8) & 0xFF,
-        mask & 0xFF
-    )
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DiagnosticStep {
+    pub name: String,
+    pub passed: bool,
+    pub latency_ms: Option<u32>,
+    pub detail: String,
 }
 
-fn ps_escape_single_quoted(input: &str) -> String {
-    input.replace('\'', "''")
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NetworkDiagnostics {
+    pub steps: Vec<DiagnosticStep>,
+    pub default_route_count: u32,
+    pub healthy: bool,
+    pub verdict: String,
 }
 
-fn cache_cleanup_recipe(target: &str) -> Option<(&'static str, &'static str)> {
-    match target {
-        "user_temp" => Some((
-            "User Temp",
-            r#"
-$ErrorActionPreference='SilentlyContinue'
-Remove-Item -Path (Join-Path $env:LOCALAPPDATA 'Temp\*') -Recurse -Force -ErrorAction SilentlyContinue
-Write-Output '[OK] User Temp cleaned.'
-"#,
-        )),
-        "windows_temp" => Some((
-            "Windows Temp",
-            r#"
-$ErrorActionPreference='SilentlyContinue'
-Remove-Item -Path (Join-Path $env:WINDIR 'Temp\*') -Recurse -Force -ErrorAction SilentlyContinue
-Write-Output '[OK] Windows Temp cleaned.'
-"#,
-        )),
-        "windows_update_cache" => Some((
-            "Windows Update Cache",
-            r#"
-$ErrorActionPreference='SilentlyContinue'
-Stop-Service -Name wuauserv -Force -ErrorAction SilentlyContinue
-Stop-Service -Name bits -Force -ErrorAction SilentlyContinue
-Remove-Item -Path (Join-Path $env:WINDIR 'SoftwareDistribution\Download\*') -Recurse -Force -ErrorAction SilentlyContinue
-Start-Service -Name wuauserv -ErrorAction SilentlyContinue
-Start-Service -Name bits -ErrorAction SilentlyContinue
-Write-Output '[OK] Windows Update cache cleaned.'
-"#,
-        )),
-        "prefetch" => Some((
-            "Prefetch",
-            r#"
-$ErrorActionPreference='SilentlyContinue'
-Remove-Item -Path (Join-Path $env:WINDIR 'Prefetch\*') -Recurse -Force -ErrorAction SilentlyContinue
-Write-Output '[OK] Prefetch cleaned.'
-"#,
-        )),
-        "explorer_cache" => Some((
-            "Explorer Cache (thumbnail/icon)",
-            r#"
-$ErrorActionPreference='SilentlyContinue'
-Remove-Item -Path (Join-Path $env:LOCALAPPDATA 'Microsoft\Windows\Explorer\thumbcache_*.db') -Force -ErrorAction SilentlyContinue
-Remove-Item -Path (Join-Path $env:LOCALAPPDATA 'Microsoft\Windows\Explorer\iconcache_*.db') -Force -ErrorAction SilentlyContinue
-Start-Process -FilePath ie4uinit.exe -ArgumentList '-ClearIconCache' -NoNewWindow -Wait -ErrorAction SilentlyContinue
-Write-Output '[OK] Explorer thumbnail/icon cache cleaned.'
-"#,
-        )),
-        "edge_cache" => Some((
-            "Microsoft Edge Cache",
-            r#"
-$ErrorActionPreference='SilentlyContinue'
-$base = Join-Path $env:LOCALAPPDATA 'Microsoft\Edge\User Data\Default'
-Remove-Item -Path (Join-Path $base 'Cache\*') -Recurse -Force -ErrorAction SilentlyContinue
-Remove-Item -Path (Join-Path $base 'Code Cache\*') -Recurse -Force -ErrorAction SilentlyContinue
-Remove-Item -Path (Join-Path $base 'GPUCache\*') -Recurse -Force -ErrorAction SilentlyContinue
-Write-Output '[OK] Microsoft Edge cache cleaned.'
-"#,
-        )),
-        "chrome_cache" => Some((
-            "Google Chrome Cache",
-            r#"
-$ErrorActionPreference='SilentlyContinue'
-$base = Join-Path $env:LOCALAPPDATA 'Google\Chrome\User Data\Default'
-Remove-Item -Path (Join-Path $base 'Cache\*') -Recurse -Force -ErrorAction SilentlyContinue
-Remove-Item -Path (Join-Path $base 'Code Cache\*') -Recurse -Force -ErrorAction SilentlyContinue
-Remove-Item -Path (Join-Path $base 'GPUCache\*') -Recurse -Force -ErrorAction SilentlyContinue
-Write-Output '[OK] Google Chrome cache cleaned.'
-"#,
-        )),
-        "firefox_cache" => Some((
-            "Mozilla Firefox Cache",
-            r#"
-$ErrorActionPreference='SilentlyContinue'
-$profiles = Join-Path $env:LOCALAPPDATA 'Mozilla\Firefox\Profiles'
-Remove-Item -Path "$profiles\*\cache2\*" -Recurse -Force -ErrorAction SilentlyContinue
-Write-Output '[OK] Mozilla Firefox cache cleaned.'
-"#,
-        )),
-        "inet_cache" => Some((
-            "INetCache",
-            r#"
-$ErrorActionPreference='SilentlyContinue'
-Remove-Item -Path (Join-Path $env:LOCALAPPDATA 'Microsoft\Windows\INetCache\*') -Recurse -Force -ErrorAction SilentlyContinue
-Write-Output '[OK] INetCache cleaned.'
-"#,
-        )),
-        "web_cache" => Some((
-            "WebCache",
-            r#"
-$ErrorActionPreference='SilentlyContinue'
-Remove-Item -Path (Join-Path $env:LOCALAPPDATA 'Microsoft\Windows\WebCache\*') -Recurse -Force -ErrorAction SilentlyContinue
-Write-Output '[OK] WebCache cleaned.'
-"#,
-        )),
-        "crash_dumps" => Some((
-            "Crash Dumps",
-            r#"
-$ErrorActionPreference='SilentlyContinue'
-Remove-Item -Path (Join-Path $env:LOCALAPPDATA 'CrashDumps\*') -Recurse -Force -ErrorAction SilentlyContinue
-Write-Output '[OK] Crash dumps cleaned.'
-"#,
-        )),
-        "wer_reports" => Some((
-            "Windows Error Reporting (WER)",
-            r#"
-$ErrorActionPreference='SilentlyContinue'
-Remove-Item -Path (Join-Path $env:ProgramData 'Microsoft\Windows\WER\*') -Recurse -Force -ErrorAction SilentlyContinue
-Remove-Item -Path (Join-Path $env:LOCALAPPDATA 'Microsoft\Windows\WER\*') -Recurse -Force -ErrorAction SilentlyContinue
-Write-Output '[OK] Windows Error Reporting (WER) cache cleaned.'
-"#,
-        )),
-        "d3d_shader_cache" => Some((
-            "DirectX Shader Cache (D3DSCache)",
-            r#"
-$ErrorActionPreference='SilentlyContinue'
-Remove-Item -Path (Join-Path $env:LOCALAPPDATA 'D3DSCache\*') -Recurse -Force -ErrorAction SilentlyContinue
-Write-Output '[OK] DirectX Shader Cache cleaned.'
-"#,
-        )),
-        _ => None,
-    }
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AuditEntry {
+    pub timestamp: u64,
+    pub operation: String,
+    pub parameters: serde_json::Value,
+    pub success: bool,
+    pub output: String,
 }
 
-fn parse_ping_latency(stdout: &str, elapsed_ms: u32) -> u32 {
-    if stdout.contains("time=") {
-        stdout
-            .split("time=")
-            .nth(1)
-            .and_then(|s| s.split("ms").next())
-            .and_then(|s| s.trim().parse::<u32>().ok())
-            .unwrap_or(elapsed_ms)
-    } else if stdout.contains("time<") {
-        1
-    } else {
-        0
-    }
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RuntimeCapabilities {
+    pub degraded: bool,
+    pub available_features: Vec<String>,
+    pub unavailable_features: Vec<String>,
 }
 
-fn ping_once_target(target: String, timeout_ms: &str) -> FpingHostResult {
-    let start = Instant::now();
-    let output = Command::new("ping")
-        .args(["-n", "1", "-w", timeout_ms, &target])
-        .creation_flags(CREATE_NO_WINDOW)
-        .output();
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ConnectivityStatus {
+    pub ipv4: bool,
+    pub ipv6: bool,
+    pub dns: bool,
+}
 
-    match output {
-        Ok(out) => {
-            let stdout = String::from_utf8_lossy(&out.stdout).to_string();
-            let elapsed = start.elapsed().as_millis() as u32;
-            let latency = parse_ping_latency(&stdout, elapsed);
-            FpingHostResult {
-                target,
-                success: stdout.contains("Reply from") || stdout.contains("time="),
-                latency_ms: latency,
-                output: stdout,
-            }
-        }
-        Err(e) => FpingHostResult {
-            target,
-            success: false,
-            latency_ms: 0,
-            output: format!("Ping failed: {}", e),
-        },
-    }
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GatewayStatus {
+    pub gateway: String,
+    pub reachable: bool,
+    pub latency_ms: u32,
 }
 
-// ======================== TAURI COMMANDS ========================
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ServiceLatency {
+    pub name: String,
+    pub endpoint: String,
+    pub latency_ms: u32,
+    pub reachable: bool,
+}
 
-/// Get list of active network interfaces (NICs)
-#[tauri::command]
-pub async fn get_network_interfaces(active_only: bool) -> Result<Vec<NetworkInterface>, String> {
-    let ps_script = r#"
-        Get-WmiObject Win32_NetworkAdapterConfiguration |
-        Where-Object { $_.InterfaceIndex -ne $null } |
-        Select-Object InterfaceIndex, Description, IPAddress, DefaultIPGateway |
-        ConvertTo-Json -Compress
-    "#;
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TcpTestResult {
+    pub computer: String,
+    pub remote_address: String,
+    pub port_open: bool,
+    pub ping_succeeded: bool,
+    pub ping_latency_ms: u32,
+}
 
-    let output = run_powershell(ps_script)?;
-    let data: serde_json::Value =
-        serde_json::from_str(&output).map_err(|e| format!("JSON parse error: {}", e))?;
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DhcpRenewResult {
+    pub success: bool,
+    pub interface_alias: Option<String>,
+    pub before_ips: Vec<String>,
+    pub after_ips: Vec<String>,
+    pub output: String,
+}
 
-    let items = match &data {
-        serde_json::Value::Array(arr) => arr.clone(),
-        obj @ serde_json::Value::Object(_) => vec![obj.clone()],
-        _ => return Ok(vec![]),
-    };
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BloatwareProgress {
+    pub package: String,
+    pub label: String,
+    pub index: u32,
+    pub total: u32,
+    pub status: String,
+}
 
-    let blacklist = [
-        "virtual", "vmware", "vbox", "loopback", "wintun", "kernel",
-        "miniport", "wi-fi direct", "tap-", "pseudo", "ethernet adapter v",
-    ];
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CacheProgress {
+    pub target: String,
+    pub label: String,
+    pub index: u32,
+    pub total: u32,
+    pub status: String,
+}
 
-    let mut interfaces: Vec<NetworkInterface> = Vec::new();
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ScheduledTask {
+    pub name: String,
+    pub path: String,
+    pub state: String,
+    pub description: String,
+}
 
-    for item in &items {
-        let desc = item["Description"].as_str().unwrap_or("").to_string();
-        let desc_lower = desc.to_lowercase();
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WindowsService {
+    pub name: String,
+    pub display_name: String,
+    pub status: String,
+    pub start_type: String,
+}
 
-        if blacklist.iter().any(|b| desc_lower.contains(b)) {
-            continue;
-        }
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StartupItem {
+    pub name: String,
+    pub command: String,
+    pub location: String,
+    pub enabled: bool,
+}
 
-        let ip = match &item["IPAddress"] {
-            serde_json::Value::Array(arr) => {
-                arr.first()
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("0.0.0.0")
-                    .to_string()
-            }
-            serde_json::Value::String(s) => s.clone(),
-            _ => "0.0.0.0".to_string(),
-        };
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReverseDnsResult {
+    pub ip: String,
+    pub hostname: String,
+}
 
-        if active_only && (ip.is_empty() || ip == "0.0.0.0") {
-            continue;
-        }
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LanDevice {
+    pub ip: String,
+    pub mac: String,
+    pub hostname: String,
+    pub vendor: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DnsCacheEntry {
+    pub name: String,
+    pub record_type: String,
+    pub ttl: u32,
+    pub data: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ConnectionQualitySample {
+    pub second: u32,
+    pub gateway: GatewayStatus,
+    pub internet_reachable: bool,
+    pub internet_latency_ms: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ConnectionQualityReport {
+    pub samples: u32,
+    pub successful: u32,
+    pub uptime_percent: f32,
+    pub avg_latency_ms: u32,
+    pub max_gap_secs: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RouteLookup {
+    pub route: RouteEntry,
+    pub source_ip: String,
+    pub interface_index: String,
+    pub interface_alias: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MtuProbeResult {
+    pub target: String,
+    pub mtu: u32,
+    pub largest_payload: u32,
+    pub applied: bool,
+    pub output: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NeighborEntry {
+    pub ip: String,
+    pub mac: String,
+    pub interface_index: String,
+    pub state: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FirewallProfile {
+    pub name: String,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DnsBenchmark {
+    pub server: String,
+    pub latency_ms: u32,
+    pub success: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PowerPlan {
+    pub guid: String,
+    pub name: String,
+    pub active: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BatteryStatus {
+    pub charge_percent: u8,
+    pub on_ac_power: bool,
+    pub time_remaining_mins: Option<u32>,
+    pub charging: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PingSample {
+    pub seq: u32,
+    pub success: bool,
+    pub latency_ms: u32,
+    pub ttl: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct IpConflict {
+    pub ip: String,
+    pub local_mac: String,
+    /// `None` when Windows flagged the address as duplicate but no other MAC was seen
+    pub conflicting_mac: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AddressBackup {
+    pub ip: String,
+    pub prefix_length: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InterfaceConfigBackup {
+    pub interface_index: String,
+    pub interface_alias: String,
+    pub dhcp: bool,
+    pub addresses: Vec<AddressBackup>,
+    pub gateway: Option<String>,
+    pub dns_static: bool,
+    pub dns_servers: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NetworkConfigBackup {
+    pub created_at: u64,
+    pub interfaces: Vec<InterfaceConfigBackup>,
+    pub persistent_routes: Vec<RouteSpec>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TraceHop {
+    pub hop: u32,
+    /// Empty when every probe for this hop timed out
+    pub address: String,
+    pub latencies_ms: Vec<Option<u32>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PathReport {
+    pub target: String,
+    pub resolved_ip: String,
+    pub chosen_route: RouteEntry,
+    pub source_ip: String,
+    pub interface_alias: String,
+    pub hops: Vec<TraceHop>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InterfaceAddress {
+    pub ip: String,
+    pub prefix_length: u32,
+    pub address_family: String,
+    pub origin: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PortCheck {
+    pub open: bool,
+    pub latency_ms: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Ipv6BindingState {
+    pub interface_index: String,
+    pub interface_alias: String,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InterfaceResetResult {
+    pub success: bool,
+    pub interface_index: String,
+    pub interface_alias: String,
+    pub before_ips: Vec<String>,
+    pub after_ips: Vec<String>,
+    pub output: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CaptivePortalStatus {
+    pub captive: bool,
+    pub redirect_url: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WindowsVersion {
+    pub build: u32,
+    pub display_version: String,
+    pub edition: String,
+    pub is_server: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProcessInfo {
+    pub pid: u32,
+    pub name: String,
+    pub memory_mb: f64,
+    pub cpu_percent: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SystemUptime {
+    pub last_boot: String,
+    pub uptime_secs: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DiskInfo {
+    pub drive: String,
+    pub total_bytes: u64,
+    pub free_bytes: u64,
+    pub used_percent: f32,
+}
+
+/// One cache-cleanup target. Every entry in `paths` is an (environment variable,
+/// relative glob) pair deleted recursively; `before`/`after` hold the extra steps a
+/// few targets need (stopping services, rebuilding the icon cache, wsreset).
+struct CacheRecipe {
+    id: &'static str,
+    label: &'static str,
+    description: &'static str,
+    paths: &'static [(&'static str, &'static str)],
+    before: &'static str,
+    after: &'static str,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CacheTargetInfo {
+    pub id: String,
+    pub label: String,
+    pub description: String,
+    pub paths: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SubnetInfo {
+    pub network: String,
+    pub broadcast: String,
+    pub first_host: String,
+    pub last_host: String,
+    pub usable_hosts: u32,
+    pub prefix_length: u32,
+}
+
+/// User-tunable defaults, stored as JSON in the app data directory. Missing
+/// fields (e.g. from an older file) fall back to `Preferences::default()`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct Preferences {
+    pub ping_timeout_ms: u32,
+    /// `None` keeps fping_scan's CPU-based worker heuristic
+    pub fping_max_parallel: Option<usize>,
+    pub default_interface_index: Option<String>,
+    pub confirm_destructive_ops: bool,
+}
+
+impl Default for Preferences {
+    fn default() -> Self {
+        Preferences {
+            ping_timeout_ms: 2000,
+            fping_max_parallel: None,
+            default_interface_index: None,
+            confirm_destructive_ops: true,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GatewayPreview {
+    pub on_link: bool,
+    pub reachable: bool,
+    pub latency_ms: Option<u32>,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ArpFlushResult {
+    pub success: bool,
+    pub removed: u32,
+    pub output: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProcessNetUsage {
+    pub pid: u32,
+    pub process_name: String,
+    pub connection_count: u32,
+    pub listening_ports: Vec<u16>,
+    pub established_count: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TemporaryRoute {
+    pub id: String,
+    pub destination: String,
+    pub mask: String,
+    pub gateway: String,
+    /// Unix timestamp (seconds) at which the route is removed
+    pub expires_at: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HostResolution {
+    pub name: String,
+    /// IPv4 addresses first, then IPv6
+    pub addresses: Vec<String>,
+    pub ipv4_addresses: Vec<String>,
+    pub ipv6_addresses: Vec<String>,
+    pub canonical_name: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RouteAuditResult {
+    pub route: RouteEntry,
+    pub interface_exists: bool,
+    /// `None` for on-link routes, which have no gateway to ping
+    pub gateway_reachable: Option<bool>,
+    pub issue: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DnsMonitorSample {
+    pub second: u32,
+    pub success: bool,
+    pub latency_ms: u32,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DnsMonitorReport {
+    pub attempts: u32,
+    pub successes: u32,
+    pub avg_latency_ms: u32,
+    pub failures: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EventLogEntry {
+    pub time: String,
+    pub level: String,
+    pub provider: String,
+    pub event_id: u32,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PathMtuResult {
+    pub path_mtu: u32,
+    /// The path could not carry a full 1500-byte packet unfragmented
+    pub fragmentation_occurred: bool,
+    /// (packet size incl. 28-byte IP/ICMP header, answered) in probe order
+    pub probes: Vec<(u32, bool)>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EffectiveDns {
+    pub interface_alias: String,
+    pub servers: Vec<String>,
+    pub metric: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RouteDiff {
+    pub added: Vec<RouteEntry>,
+    pub removed: Vec<RouteEntry>,
+    /// (snapshot, live) for routes whose gateway or metric changed
+    pub changed: Vec<(RouteEntry, RouteEntry)>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PingPercentiles {
+    pub p50: u32,
+    pub p90: u32,
+    pub p99: u32,
+    pub min: u32,
+    pub max: u32,
+    pub loss_percent: f32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DohConfig {
+    pub interface_index: String,
+    pub interface_alias: String,
+    pub server: String,
+    pub template: String,
+    pub auto_upgrade: bool,
+    pub allow_fallback_to_udp: bool,
+    /// The interface is flagged to use the server's DoH template
+    pub enabled: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StackHealth {
+    pub loopback_v4: bool,
+    pub loopback_v6: bool,
+    pub tcp_loopback: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InterfaceMetric {
+    pub interface_index: String,
+    /// Effective IPv4 interface metric (lower is preferred)
+    pub metric: u32,
+    pub automatic: bool,
+}
+
+// ======================== HELPERS ========================
+
+/// Run `command` to completion, killing it if it outlives `timeout`.
+/// The pipes are drained on helper threads so a chatty child can't block.
+fn output_with_timeout(
+    command: &mut Command,
+    label: &str,
+    timeout: Duration,
+) -> Result<Output, NetworkError> {
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| {
+            NetworkError::command_failed(label, format!("Failed to run {}: {}", label, e))
+        })?;
+
+    let stdout_pipe = child.stdout.take();
+    let stderr_pipe = child.stderr.take();
+    let stdout_reader = thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(mut pipe) = stdout_pipe {
+            let _ = pipe.read_to_end(&mut buf);
+        }
+        buf
+    });
+    let stderr_reader = thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(mut pipe) = stderr_pipe {
+            let _ = pipe.read_to_end(&mut buf);
+        }
+        buf
+    });
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if Instant::now() >= deadline => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(NetworkError::Timeout {
+                    command: label.to_string(),
+                    timeout_secs: timeout.as_secs(),
+                });
+            }
+            Ok(None) => thread::sleep(Duration::from_millis(50)),
+            Err(e) => {
+                return Err(NetworkError::command_failed(
+                    label,
+                    format!("Failed to wait for {}: {}", label, e),
+                ))
+            }
+        }
+    };
+
+    Ok(Output {
+        status,
+        stdout: stdout_reader.join().unwrap_or_default(),
+        stderr: stderr_reader.join().unwrap_or_default(),
+    })
+}
+
+fn powershell_output(script: &str, timeout: Duration) -> Result<Output, NetworkError> {
+    let full_script = format!("{}\n{}", PS_UTF8_PREAMBLE, script);
+    output_with_timeout(
+        Command::new("powershell")
+            .args(["-NoProfile", "-NonInteractive", "-Command", &full_script])
+            .creation_flags(CREATE_NO_WINDOW),
+        "PowerShell",
+        timeout,
+    )
+}
+
+fn decode_utf8_output(bytes: &[u8]) -> String {
+    let text = String::from_utf8_lossy(bytes);
+    text.strip_prefix('\u{feff}').unwrap_or(&text).to_string()
+}
+
+fn run_powershell(script: &str) -> Result<String, NetworkError> {
+    run_powershell_with_timeout(script, DEFAULT_POWERSHELL_TIMEOUT)
+}
+
+fn run_powershell_with_timeout(script: &str, timeout: Duration) -> Result<String, NetworkError> {
+    let output = powershell_output(script, timeout)?;
+
+    if output.status.success() {
+        Ok(decode_utf8_output(&output.stdout))
+    } else {
+        let stderr = decode_utf8_output(&output.stderr);
+        let stdout = decode_utf8_output(&output.stdout);
+        // Some commands write to stdout even on "failure"
+        if !stdout.is_empty() {
+            Ok(stdout)
+        } else {
+            Err(NetworkError::command_failed("PowerShell", stderr))
+        }
+    }
+}
+
+/// Like run_powershell, but retries on a non-zero exit up to `attempts` times,
+/// waiting `delay`, `2 * delay`, ... between tries. For scripts that touch
+/// services which can be briefly locked.
+fn run_powershell_retry(
+    script: &str,
+    attempts: u32,
+    delay: Duration,
+) -> Result<String, NetworkError> {
+    let attempts = attempts.max(1);
+    let mut last_error = String::new();
+
+    for attempt in 1..=attempts {
+        let output = powershell_output(script, DEFAULT_POWERSHELL_TIMEOUT)?;
+
+        if output.status.success() {
+            return Ok(decode_utf8_output(&output.stdout));
+        }
+
+        let stderr = decode_utf8_output(&output.stderr).trim().to_string();
+        let stdout = decode_utf8_output(&output.stdout).trim().to_string();
+        last_error = if stderr.is_empty() { stdout } else { stderr };
+
+        if attempt < attempts {
+            thread::sleep(delay * attempt);
+        }
+    }
+
+    Err(NetworkError::command_failed(
+        "PowerShell",
+        format!(
+            "PowerShell failed after {} attempt(s): {}",
+            attempts, last_error
+        ),
+    ))
+}
+
+fn run_cmd(program: &str, args: &[&str]) -> Result<String, NetworkError> {
+    run_cmd_with_timeout(program, args, DEFAULT_CMD_TIMEOUT)
+}
+
+fn run_cmd_with_timeout(
+    program: &str,
+    args: &[&str],
+    timeout: Duration,
+) -> Result<String, NetworkError> {
+    let output = output_with_timeout(
+        Command::new(program)
+            .args(args)
+            .creation_flags(CREATE_NO_WINDOW),
+        program,
+        timeout,
+    )?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+
+    if output.status.success() {
+        Ok(stdout)
+    } else if !stdout.is_empty() {
+        Ok(format!("{}\n{}", stdout, stderr))
+    } else {
+        Err(NetworkError::command_failed(
+            format!("{} {}", program, args.join(" ")),
+            stderr,
+        ))
+    }
+}
+
+fn prefix_to_mask(prefix: u32) -> String {
+    if prefix > 32 {
+        return "255.255.255.255".to_string();
+    }
+    let mask: u32 = if prefix == 0 {
+        0
+    } else {
+        0xFFFFFFFF << (32 - prefix)
+    };
+    format!(
+        "{}.{}.{}.{}",
+        (mask >> 24) & 0xFF,
+        (mask >> 16) & 0xFF,
+        (mask >> 8) & 0xFF,
+        mask & 0xFF
+    )
+}
+
+/// Inverse of `prefix_to_mask`; rejects non-contiguous masks like 255.0.255.0
+fn mask_to_prefix(mask: &str) -> Result<u32, NetworkError> {
+    let bits = u32::from(
+        mask.trim()
+            .parse::<std::net::Ipv4Addr>()
+            .map_err(|_| NetworkError::invalid(format!("Invalid subnet mask: {}", mask)))?,
+    );
+    let prefix = bits.leading_ones();
+    if bits.checked_shl(prefix).unwrap_or(0) != 0 {
+        return Err(NetworkError::invalid(format!(
+            "Invalid subnet mask: {}",
+            mask
+        )));
+    }
+    Ok(prefix)
+}
+
+/// Interface indexes end up in route.exe/PowerShell command lines, so every
+/// command taking one runs it through here first.
+fn validate_interface_index(idx: &str) -> Result<String, NetworkError> {
+    let trimmed = idx.trim();
+    if trimmed.is_empty() {
+        return Err(NetworkError::invalid("Interface index is required"));
+    }
+    if !trimmed.chars().all(|ch| ch.is_ascii_digit()) {
+        return Err(NetworkError::invalid(format!(
+            "Invalid interface index: {}",
+            trimmed
+        )));
+    }
+    trimmed
+        .parse::<u32>()
+        .map(|value| value.to_string())
+        .map_err(|_| NetworkError::invalid(format!("Invalid interface index: {}", trimmed)))
+}
+
+fn ps_escape_single_quoted(input: &str) -> String {
+    input.replace('\'', "''")
+}
+
+fn normalize_firewall_direction(direction: &str) -> Option<&'static str> {
+    match direction.trim().to_lowercase().as_str() {
+        "inbound" | "in" => Some("Inbound"),
+        "outbound" | "out" => Some("Outbound"),
+        _ => None,
+    }
+}
+
+fn normalize_firewall_action(action: &str) -> Option<&'static str> {
+    match action.trim().to_lowercase().as_str() {
+        "allow" => Some("Allow"),
+        "block" => Some("Block"),
+        _ => None,
+    }
+}
+
+fn normalize_firewall_protocol(protocol: &str) -> Option<&'static str> {
+    match protocol.trim().to_lowercase().as_str() {
+        "tcp" => Some("TCP"),
+        "udp" => Some("UDP"),
+        _ => None,
+    }
+}
+
+fn read_proxy_settings() -> Result<ProxySettings, NetworkError> {
+    let ps_script = format!(
+        r#"
+        Get-ItemProperty -Path '{INTERNET_SETTINGS_KEY}' |
+        Select-Object ProxyEnable, ProxyServer, ProxyOverride |
+        ConvertTo-Json -Compress
+    "#
+    );
+
+    let output = run_powershell(&ps_script)?;
+    let data: serde_json::Value = serde_json::from_str(output.trim())
+        .map_err(|e| NetworkError::parse(format!("JSON parse error: {}", e)))?;
+
+    let enabled = match &data["ProxyEnable"] {
+        serde_json::Value::Number(n) => n.as_u64().unwrap_or(0) != 0,
+        serde_json::Value::Bool(b) => *b,
+        _ => false,
+    };
+    let server = data["ProxyServer"].as_str().unwrap_or("").trim().to_string();
+    let bypass_list = data["ProxyOverride"]
+        .as_str()
+        .unwrap_or("")
+        .split(';')
+        .map(|entry| entry.trim().to_string())
+        .filter(|entry| !entry.is_empty())
+        .collect();
+
+    Ok(ProxySettings {
+        enabled,
+        server,
+        bypass_list,
+    })
+}
+
+impl CacheRecipe {
+    /// Paths as the user would type them, e.g. `%LOCALAPPDATA%\Temp\*`
+    fn display_paths(&self) -> Vec<String> {
+        self.paths
+            .iter()
+            .map(|(var, rel)| format!("%{}%\\{}", var, rel))
+            .collect()
+    }
+
+    fn script(&self) -> String {
+        let mut script = String::from("\n$ErrorActionPreference='SilentlyContinue'\n");
+        script.push_str(self.before.trim_start_matches('\n'));
+        for (var, rel) in self.paths {
+            script.push_str(&format!(
+                "Remove-Item -Path (Join-Path $env:{} '{}') -Recurse -Force -ErrorAction SilentlyContinue\n",
+                var, rel
+            ));
+        }
+        script.push_str(self.after.trim_start_matches('\n'));
+        script.push_str(&format!("Write-Output '[OK] {} cleaned.'\n", self.label));
+        script
+    }
+}
+
+fn cache_cleanup_recipe(target: &str) -> Option<&'static CacheRecipe> {
+    CACHE_RECIPES.iter().find(|recipe| recipe.id == target)
+}
+
+/// (latency ms, TTL) of the first reply in ping output. TTL is 0 when absent,
+/// as in IPv6 replies.
+fn parse_ping_latency(stdout: &str, elapsed_ms: u32) -> (u32, u32) {
+    let latency = if stdout.contains("time=") {
+        stdout
+            .split("time=")
+            .nth(1)
+            .and_then(|s| s.split("ms").next())
+            .and_then(|s| s.trim().parse::<u32>().ok())
+            .unwrap_or(elapsed_ms)
+    } else if stdout.contains("time<") {
+        1
+    } else {
+        0
+    };
+    let ttl = stdout
+        .split("TTL=")
+        .nth(1)
+        .map(|s| s.chars().take_while(|ch| ch.is_ascii_digit()).collect::<String>())
+        .and_then(|s| s.parse::<u32>().ok())
+        .unwrap_or(0);
+    (latency, ttl)
+}
+
+fn ping_once_target(target: String, timeout_ms: &str, count: u32) -> FpingHostResult {
+    ping_target_from(target, timeout_ms, count, None)
+}
+
+/// Same as `ping_once_target`, optionally forcing the source address with `-S`
+fn ping_target_from(
+    target: String,
+    timeout_ms: &str,
+    count: u32,
+    source_ip: Option<&str>,
+) -> FpingHostResult {
+    let count = count.max(1);
+    let count_arg = count.to_string();
+    let mut args = vec!["-n", &count_arg, "-w", timeout_ms];
+    if let Some(source) = source_ip {
+        args.extend(["-S", source]);
+    }
+    args.push(&target);
+    let start = Instant::now();
+    let output = Command::new("ping")
+        .args(&args)
+        .creation_flags(CREATE_NO_WINDOW)
+        .output();
+
+    match output {
+        Ok(out) => {
+            let stdout = String::from_utf8_lossy(&out.stdout).to_string();
+            let elapsed = start.elapsed().as_millis() as u32 / count;
+            let reply_latencies = parse_reply_latencies(&stdout, elapsed);
+            let received = (reply_latencies.len() as u32).min(count);
+            let latency = if reply_latencies.is_empty() {
+                0
+            } else {
+                reply_latencies.iter().sum::<u32>() / reply_latencies.len() as u32
+            };
+            FpingHostResult {
+                target,
+                success: received > 0,
+                latency_ms: latency,
+                output: stdout,
+                sent: count,
+                received,
+                loss_percent: ((count - received) as f32 / count as f32) * 100.0,
+            }
+        }
+        Err(e) => FpingHostResult {
+            target,
+            success: false,
+            latency_ms: 0,
+            output: format!("Ping failed: {}", e),
+            sent: count,
+            received: 0,
+            loss_percent: 100.0,
+        },
+    }
+}
+
+/// Per-reply round-trip times from ping output, in reply order
+fn parse_reply_latencies(stdout: &str, elapsed_ms: u32) -> Vec<u32> {
+    stdout
+        .lines()
+        .filter(|line| line.contains("time=") || line.contains("time<"))
+        .map(|line| parse_ping_latency(line, elapsed_ms).0)
+        .collect()
+}
+
+struct HttpResponse {
+    status: u16,
+    headers: HashMap<String, String>,
+    stream: TcpStream,
+    body_prefix: Vec<u8>,
+}
+
+fn parse_http_url(url: &str) -> Result<(String, u16, String), NetworkError> {
+    let rest = url
+        .trim()
+        .strip_prefix("http://")
+        .ok_or_else(|| NetworkError::invalid("Only http:// URLs are supported"))?;
+    let (authority, path) = match rest.find('/') {
+        Some(pos) => (&rest[..pos], &rest[pos..]),
+        None => (rest, "/"),
+    };
+    let (host, port) = match authority.rsplit_once(':') {
+        Some((host, port)) => (
+            host,
+            port.parse::<u16>()
+                .map_err(|_| NetworkError::invalid(format!("Invalid port in URL: {}", port)))?,
+        ),
+        None => (authority, 80),
+    };
+
+    let is_safe_host = !host.is_empty()
+        && host
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '.' || ch == '-');
+    if !is_safe_host {
+        return Err(NetworkError::invalid(format!(
+            "Invalid host in URL: {}",
+            host
+        )));
+    }
+    if path.chars().any(|ch| ch.is_whitespace() || ch.is_control()) {
+        return Err(NetworkError::invalid(
+            "URL path must not contain whitespace",
+        ));
+    }
+
+    Ok((host.to_string(), port, path.to_string()))
+}
+
+/// Minimal HTTP/1.1 GET over a raw socket. The body is left on the stream
+/// (minus whatever arrived with the headers) so callers can stream it.
+fn http_get(url: &str, timeout: Duration) -> Result<HttpResponse, NetworkError> {
+    let (host, port, path) = parse_http_url(url)?;
+    let addr = (host.as_str(), port)
+        .to_socket_addrs()
+        .map_err(|e| NetworkError::io(format!("Failed to resolve {}: {}", host, e)))?
+        .next()
+        .ok_or_else(|| NetworkError::not_found(format!("No address found for {}", host)))?;
+
+    let mut stream = TcpStream::connect_timeout(&addr, timeout)
+        .map_err(|e| NetworkError::io(format!("Failed to connect to {}: {}", host, e)))?;
+    let _ = stream.set_read_timeout(Some(timeout));
+    let _ = stream.set_write_timeout(Some(timeout));
+
+    let request = format!(
+        "GET {path} HTTP/1.1\r\nHost: {host}\r\nUser-Agent: SuperRoutePro\r\nAccept: */*\r\nConnection: close\r\n\r\n"
+    );
+    stream
+        .write_all(request.as_bytes())
+        .map_err(|e| NetworkError::io(format!("Failed to send HTTP request: {}", e)))?;
+
+    let mut buffer: Vec<u8> = Vec::with_capacity(4096);
+    let mut chunk = [0u8; 4096];
+    let header_end = loop {
+        if let Some(pos) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos;
+        }
+        if buffer.len() > 64 * 1024 {
+            return Err(NetworkError::parse("HTTP response headers too large"));
+        }
+        let read = stream
+            .read(&mut chunk)
+            .map_err(|e| NetworkError::io(format!("Failed to read HTTP response: {}", e)))?;
+        if read == 0 {
+            return Err(NetworkError::io(
+                "Connection closed before HTTP headers were received",
+            ));
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    };
+
+    let head = String::from_utf8_lossy(&buffer[..header_end]).to_string();
+    let mut lines = head.lines();
+    let status = lines
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|code| code.parse::<u16>().ok())
+        .ok_or_else(|| NetworkError::parse("Invalid HTTP status line"))?;
+    let headers = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(key, value)| (key.trim().to_lowercase(), value.trim().to_string()))
+        .collect();
+
+    Ok(HttpResponse {
+        status,
+        headers,
+        stream,
+        body_prefix: buffer[header_end + 4..].to_vec(),
+    })
+}
+
+fn read_routing_table(interface_index: Option<&str>) -> Result<Vec<RouteEntry>, NetworkError> {
+    let filter = match interface_index {
+        Some(idx) => format!(
+            "Where-Object InterfaceIndex -eq {} |",
+            validate_interface_index(idx)?
+        ),
+        None => String::new(),
+    };
+    let ps_script = format!(
+        r#"
+        Get-NetRoute -AddressFamily IPv4 |
+        {filter}
+        Select-Object DestinationPrefix, NextHop, RouteMetric, InterfaceIndex |
+        ConvertTo-Json -Compress
+    "#
+    );
+
+    let output = run_powershell(&ps_script)?;
+    if output.trim().is_empty() {
+        return Ok(vec![]);
+    }
+    let data: serde_json::Value = serde_json::from_str(&output)
+        .map_err(|e| NetworkError::parse(format!("JSON parse error: {}", e)))?;
+
+    let items = match &data {
+        serde_json::Value::Array(arr) => arr.clone(),
+        obj @ serde_json::Value::Object(_) => vec![obj.clone()],
+        _ => return Ok(vec![]),
+    };
+
+    Ok(items.iter().map(route_entry_from_json).collect())
+}
+
+/// Build a `RouteEntry` from a `Get-NetRoute`-shaped JSON object
+fn route_entry_from_json(item: &serde_json::Value) -> RouteEntry {
+    let prefix = item["DestinationPrefix"]
+        .as_str()
+        .unwrap_or("")
+        .to_string();
+    let next_hop = item["NextHop"].as_str().unwrap_or("").to_string();
+
+    let metric = match &item["RouteMetric"] {
+        serde_json::Value::Number(n) => n.to_string(),
+        serde_json::Value::String(s) => s.clone(),
+        _ => "0".to_string(),
+    };
+
+    let if_index = match &item["InterfaceIndex"] {
+        serde_json::Value::Number(n) => n.to_string(),
+        serde_json::Value::String(s) => s.clone(),
+        _ => "0".to_string(),
+    };
+
+    // Split prefix into destination and mask
+    let (dest, mask) = if let Some(pos) = prefix.find('/') {
+        let ip = prefix[..pos].to_string();
+        let prefix_len: u32 = prefix[pos + 1..].parse().unwrap_or(32);
+        (ip, prefix_to_mask(prefix_len))
+    } else {
+        (prefix, "255.255.255.255".to_string())
+    };
+
+    RouteEntry {
+        destination: dest,
+        netmask: mask,
+        gateway: next_hop,
+        metric,
+        interface_index: if_index,
+    }
+}
+
+fn add_route_entry(spec: &RouteSpec) -> Result<String, NetworkError> {
+    let if_idx = match spec.interface_index.as_deref().map(str::trim) {
+        Some(idx) if !idx.is_empty() => Some(validate_interface_index(idx)?),
+        _ => None,
+    };
+
+    // First try to delete existing route
+    let _ = run_cmd("route", &["delete", &spec.destination, "mask", &spec.mask]);
+
+    let mut args = vec![
+        "-p",
+        "add",
+        &spec.destination,
+        "mask",
+        &spec.mask,
+        &spec.gateway,
+        "metric",
+        &spec.metric,
+    ];
+
+    if let Some(ref idx) = if_idx {
+        args.push("if");
+        args.push(idx);
+    }
+
+    run_cmd("route", &args)
+}
+
+fn sanitize_profile_name(name: &str) -> Result<String, NetworkError> {
+    let sanitized: String = name
+        .trim()
+        .chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() || ch == '-' || ch == '_' || ch == ' ' {
+                ch
+            } else {
+                '_'
+            }
+        })
+        .take(64)
+        .collect();
+    let sanitized = sanitized.trim().to_string();
+
+    if sanitized.is_empty() || sanitized.chars().all(|ch| ch == '_') {
+        return Err(NetworkError::invalid("Profile name is required"));
+    }
+    Ok(sanitized)
+}
+
+fn app_data_dir(app: &tauri::AppHandle) -> Result<PathBuf, NetworkError> {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| NetworkError::io(format!("Failed to resolve app data directory: {}", e)))?;
+    fs::create_dir_all(&dir).map_err(|e| {
+        NetworkError::io(format!(
+            "Failed to create app data directory: {} ({})",
+            dir.display(),
+            e
+        ))
+    })?;
+    Ok(dir)
+}
+
+fn route_profiles_dir(app: &tauri::AppHandle) -> Result<PathBuf, NetworkError> {
+    let dir = app_data_dir(app)?.join("route_profiles");
+    fs::create_dir_all(&dir).map_err(|e| {
+        NetworkError::io(format!(
+            "Failed to create profile directory: {} ({})",
+            dir.display(),
+            e
+        ))
+    })?;
+    Ok(dir)
+}
+
+fn unix_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Append one JSON line describing a route mutation to the audit log.
+/// Logging is best-effort: a failed write never fails the mutation itself.
+fn log_route_mutation(
+    app: &tauri::AppHandle,
+    operation: &str,
+    parameters: serde_json::Value,
+    result: &Result<String, NetworkError>,
+) {
+    let entry = AuditEntry {
+        timestamp: unix_timestamp(),
+        operation: operation.to_string(),
+        parameters,
+        success: result.is_ok(),
+        output: match result {
+            Ok(out) => out.trim().to_string(),
+            Err(err) => err.to_string().trim().to_string(),
+        },
+    };
+
+    let path = match app_data_dir(app) {
+        Ok(dir) => dir.join(ROUTE_AUDIT_LOG_FILE),
+        Err(_) => return,
+    };
+    if let Ok(line) = serde_json::to_string(&entry) {
+        if let Ok(mut file) = fs::OpenOptions::new().create(true).append(true).open(path) {
+            let _ = writeln!(file, "{}", line);
+        }
+    }
+}
+
+fn read_default_gateways() -> Result<Vec<DefaultGateway>, NetworkError> {
+    let ps_script = r#"
+        Get-NetRoute -AddressFamily IPv4 -DestinationPrefix 0.0.0.0/0 -ErrorAction SilentlyContinue |
+        ForEach-Object {
+            $adapter = Get-NetAdapter -InterfaceIndex $_.InterfaceIndex -ErrorAction SilentlyContinue
+            $ipInterface = Get-NetIPInterface -InterfaceIndex $_.InterfaceIndex -AddressFamily IPv4 -ErrorAction SilentlyContinue
+            [PSCustomObject]@{
+                NextHop = $_.NextHop
+                InterfaceIndex = $_.InterfaceIndex
+                InterfaceAlias = if ($adapter) { $adapter.Name } else { $_.InterfaceAlias }
+                RouteMetric = $_.RouteMetric
+                InterfaceMetric = if ($ipInterface) { $ipInterface.InterfaceMetric } else { 0 }
+            }
+        } |
+        ConvertTo-Json -Compress
+    "#;
+
+    let output = run_powershell(ps_script)?;
+    if output.trim().is_empty() {
+        return Ok(vec![]);
+    }
+    let data: serde_json::Value = serde_json::from_str(output.trim())
+        .map_err(|e| NetworkError::parse(format!("JSON parse error: {}", e)))?;
+
+    let items = match &data {
+        serde_json::Value::Array(arr) => arr.clone(),
+        obj @ serde_json::Value::Object(_) => vec![obj.clone()],
+        _ => return Ok(vec![]),
+    };
+
+    let mut gateways: Vec<DefaultGateway> = items
+        .iter()
+        .map(|item| DefaultGateway {
+            gateway: item["NextHop"].as_str().unwrap_or("").to_string(),
+            interface_index: match &item["InterfaceIndex"] {
+                serde_json::Value::Number(n) => n.to_string(),
+                serde_json::Value::String(s) => s.clone(),
+                _ => String::new(),
+            },
+            interface_alias: item["InterfaceAlias"].as_str().unwrap_or("").to_string(),
+            metric: item["RouteMetric"]
+                .as_u64()
+                .map(|route_metric| route_metric + item["InterfaceMetric"].as_u64().unwrap_or(0))
+                .unwrap_or(u32::MAX as u64)
+                .min(u32::MAX as u64) as u32,
+        })
+        .collect();
+    // Windows picks the default route with the lowest route + interface metric
+    gateways.sort_by_key(|gw| gw.metric);
+    Ok(gateways)
+}
+
+fn strip_ipconfig_suffix(value: &str) -> String {
+    // "192.168.1.10(Preferred)" -> "192.168.1.10"
+    match value.find('(') {
+        Some(pos) => value[..pos].trim().to_string(),
+        None => value.trim().to_string(),
+    }
+}
+
+fn apply_ipconfig_field(adapter: &mut AdapterConfig, key: &str, value: &str) {
+    if value.is_empty() {
+        return;
+    }
+    let key = key.to_lowercase();
+    let clean = strip_ipconfig_suffix(value);
+
+    if key.starts_with("description") {
+        adapter.description = value.to_string();
+    } else if key.starts_with("physical address") {
+        adapter.mac = value.to_string();
+    } else if key.starts_with("ipv4 address") || key.starts_with("autoconfiguration ipv4") {
+        adapter.ipv4.push(clean);
+    } else if key.contains("ipv6 address") {
+        adapter.ipv6.push(clean);
+    } else if key.starts_with("subnet mask") {
+        adapter.subnet_mask = clean;
+    } else if key.starts_with("default gateway") {
+        adapter.default_gateway.push(clean);
+    } else if key.starts_with("dns servers") {
+        adapter.dns_servers.push(clean);
+    } else if key.starts_with("dhcp server") {
+        adapter.dhcp_server = clean;
+    }
+}
+
+fn parse_ipconfig_all(text: &str) -> Vec<AdapterConfig> {
+    let mut adapters: Vec<AdapterConfig> = Vec::new();
+    let mut current: Option<AdapterConfig> = None;
+    let mut last_key = String::new();
+
+    for line in text.lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        // Adapter headers are the only unindented lines, e.g. "Ethernet adapter Ethernet:"
+        if !line.starts_with(' ') {
+            if let Some(adapter) = current.take() {
+                adapters.push(adapter);
+            }
+            let header = line.trim().trim_end_matches(':').to_string();
+            if header.to_lowercase().contains("adapter") {
+                current = Some(AdapterConfig {
+                    adapter_name: header,
+                    ..Default::default()
+                });
+            }
+            last_key.clear();
+            continue;
+        }
+
+        let adapter = match current.as_mut() {
+            Some(adapter) => adapter,
+            None => continue,
+        };
+
+        if let Some((raw_key, raw_value)) = line.split_once(" : ") {
+            last_key = raw_key.trim_end_matches(['.', ' ']).trim().to_string();
+            apply_ipconfig_field(adapter, &last_key, raw_value.trim());
+        } else if !last_key.is_empty() {
+            // Continuation line for multi-value fields (DNS servers, gateways)
+            apply_ipconfig_field(adapter, &last_key, line.trim());
+        }
+    }
+
+    if let Some(adapter) = current.take() {
+        adapters.push(adapter);
+    }
+    adapters
+}
+
+/// TCP connect to `addr`, returning the connect time in ms
+fn tcp_probe(addr: &str, timeout: Duration) -> Option<u32> {
+    let socket_addr: std::net::SocketAddr = addr.parse().ok()?;
+    let start = Instant::now();
+    TcpStream::connect_timeout(&socket_addr, timeout)
+        .ok()
+        .map(|_| start.elapsed().as_millis() as u32)
+}
+
+/// TCP connect to the internet probe address, returning the connect time in ms
+fn probe_internet() -> Option<u32> {
+    tcp_probe(INTERNET_PROBE_ADDR, Duration::from_secs(3))
+}
+
+fn resolve_hostname(host: &str) -> Result<Vec<std::net::IpAddr>, NetworkError> {
+    (host, 0)
+        .to_socket_addrs()
+        .map(|addrs| addrs.map(|addr| addr.ip()).collect())
+        .map_err(|e| NetworkError::io(format!("Failed to resolve {}: {}", host, e)))
+}
+
+fn ping_step(name: &str, target: &str) -> DiagnosticStep {
+    let result = ping_once_target(target.to_string(), "2000", 1);
+    DiagnosticStep {
+        name: name.to_string(),
+        passed: result.success,
+        latency_ms: if result.success {
+            Some(result.latency_ms)
+        } else {
+            None
+        },
+        detail: if result.success {
+            format!("Reply from {}", target)
+        } else {
+            format!("No reply from {}", target)
+        },
+    }
+}
+
+pub(crate) fn set_degraded_mode(degraded: bool) {
+    DEGRADED_MODE.store(degraded, Ordering::Relaxed);
+}
+
+fn require_admin(operation: &str) -> Result<(), NetworkError> {
+    if DEGRADED_MODE.load(Ordering::Relaxed) {
+        Err(NetworkError::NotAdmin {
+            operation: operation.to_string(),
+        })
+    } else {
+        Ok(())
+    }
+}
+
+/// Run each (program, args) step in order, appending `[OK]`/`[FAIL]` lines.
+/// Returns the (ok, failed) step counts.
+fn run_command_steps(steps: &[(&str, &[&str])], output_lines: &mut Vec<String>) -> (u32, u32) {
+    let mut ok_count = 0u32;
+    let mut failed_count = 0u32;
+
+    for (program, args) in steps {
+        let label = format!("{} {}", program, args.join(" "));
+        output_lines.push(format!("[STEP] {}", label));
+        match run_cmd(program, args) {
+            Ok(out) => {
+                ok_count += 1;
+                output_lines.extend(
+                    out.trim()
+                        .lines()
+                        .filter(|line| !line.trim().is_empty())
+                        .map(|line| line.trim_end().to_string()),
+                );
+                output_lines.push(format!("[OK] {}", label));
+            }
+            Err(err) => {
+                failed_count += 1;
+                output_lines.push(format!("[FAIL] {}: {}", label, err.to_string().trim()));
+            }
+        }
+        output_lines.push(String::new());
+    }
+
+    (ok_count, failed_count)
+}
+
+/// Sleep for `duration`, waking early when `stop` is set
+/// Install a fresh stop flag in `slot`, stopping the run that held it before
+fn start_monitor_run(slot: &Mutex<Option<Arc<AtomicBool>>>) -> Arc<AtomicBool> {
+    let stop_flag = Arc::new(AtomicBool::new(false));
+    let mut guard = match slot.lock() {
+        Ok(g) => g,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    if let Some(previous) = guard.replace(Arc::clone(&stop_flag)) {
+        previous.store(true, Ordering::Relaxed);
+    }
+    stop_flag
+}
+
+/// Clear `slot` once a run ends, unless a newer run already replaced it
+fn finish_monitor_run(slot: &Mutex<Option<Arc<AtomicBool>>>, stop_flag: &Arc<AtomicBool>) {
+    let mut guard = match slot.lock() {
+        Ok(g) => g,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    if guard.as_ref().is_some_and(|current| Arc::ptr_eq(current, stop_flag)) {
+        guard.take();
+    }
+}
+
+/// Signal the run in `slot` to stop; false when none was running
+fn stop_monitor_run(slot: &Mutex<Option<Arc<AtomicBool>>>) -> bool {
+    let mut guard = match slot.lock() {
+        Ok(g) => g,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    match guard.take() {
+        Some(flag) => {
+            flag.store(true, Ordering::Relaxed);
+            true
+        }
+        None => false,
+    }
+}
+
+fn sleep_unless_stopped(duration: Duration, stop: &AtomicBool) {
+    let deadline = Instant::now() + duration;
+    while !stop.load(Ordering::Relaxed) {
+        let now = Instant::now();
+        if now >= deadline {
+            break;
+        }
+        thread::sleep((deadline - now).min(Duration::from_millis(100)));
+    }
+}
+
+/// Run `job_fn` over `jobs` on a bounded worker pool (same queue/results
+/// pattern as fping_scan). Results are returned in input order.
+fn run_ordered_pool<T, R, F>(jobs: Vec<T>, max_workers: usize, job_fn: F) -> Vec<R>
+where
+    T: Send,
+    R: Send,
+    F: Fn(T) -> R + Sync,
+{
+    let total = jobs.len();
+    let worker_count = total.min(max_workers.max(1));
+    let queue: Mutex<VecDeque<(usize, T)>> = Mutex::new(jobs.into_iter().enumerate().collect());
+    let results: Mutex<Vec<(usize, R)>> = Mutex::new(Vec::with_capacity(total));
+
+    thread::scope(|scope| {
+        for _ in 0..worker_count {
+            scope.spawn(|| loop {
+                let next_job = {
+                    let mut guard = match queue.lock() {
+                        Ok(g) => g,
+                        Err(poisoned) => poisoned.into_inner(),
+                    };
+                    guard.pop_front()
+                };
+
+                let (index, job) = match next_job {
+                    Some(job) => job,
+                    None => break,
+                };
+
+                let result = job_fn(job);
+                let mut out_guard = match results.lock() {
+                    Ok(g) => g,
+                    Err(poisoned) => poisoned.into_inner(),
+                };
+                out_guard.push((index, result));
+            });
+        }
+    });
+
+    let mut ordered = match results.into_inner() {
+        Ok(v) => v,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    ordered.sort_by_key(|(index, _)| *index);
+    ordered.into_iter().map(|(_, result)| result).collect()
+}
+
+/// Hostname or IPv4/IPv6 literal that is safe to interpolate into a command line
+fn is_safe_host_token(host: &str) -> bool {
+    !host.is_empty()
+        && host.len() <= 253
+        && host
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '.' || ch == '-' || ch == ':')
+}
+
+fn interface_alias(interface_index: &str) -> Result<String, NetworkError> {
+    let interface_index = validate_interface_index(interface_index)?;
+    let script = format!(
+        "(Get-NetAdapter -InterfaceIndex {} -ErrorAction Stop).Name",
+        interface_index
+    );
+    let alias = run_powershell(&script)?.trim().to_string();
+    if alias.is_empty() {
+        Err(NetworkError::not_found(format!(
+            "No adapter found for interface index {}",
+            interface_index
+        )))
+    } else {
+        Ok(alias)
+    }
+}
+
+/// IPv4 addresses of one interface (or every interface when `None`)
+fn interface_ipv4_addresses(interface_index: Option<&str>) -> Vec<String> {
+    let filter = match interface_index.map(validate_interface_index) {
+        Some(Ok(idx)) => format!(" -InterfaceIndex {}", idx),
+        Some(Err(_)) => return vec![],
+        None => String::new(),
+    };
+    let script = format!(
+        "Get-NetIPAddress -AddressFamily IPv4{} -ErrorAction SilentlyContinue | Where-Object {{ $_.IPAddress -ne '127.0.0.1' }} | Select-Object -ExpandProperty IPAddress",
+        filter
+    );
+    run_powershell(&script)
+        .map(|out| {
+            out.lines()
+                .map(|line| line.trim().to_string())
+                .filter(|line| !line.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BloatwareOutcome {
+    Removed,
+    Skipped,
+    Failed,
+}
+
+/// Remove one allowlisted AppX package (installed + provisioned copies).
+/// Returns the outcome and the output lines for the summary.
+fn remove_bloatware_package(package_name: &str) -> (BloatwareOutcome, Vec<String>) {
+    let escaped_name = ps_escape_single_quoted(package_name);
+    let script = format!(
+        r#"
+$pkgName = '{escaped_name}'
+$hasFailure = $false
+$removedInstalled = 0
+$removedProvisioned = 0
+
+$installedMatches = Get-AppxPackage -AllUsers -ErrorAction SilentlyContinue | Where-Object {{ $_.Name -eq $pkgName }}
+if (-not $installedMatches) {{
+  $installedMatches = Get-AppxPackage -ErrorAction SilentlyContinue | Where-Object {{ $_.Name -eq $pkgName }}
+}}
+foreach ($pkg in $installedMatches) {{
+  try {{
+    Remove-AppxPackage -Package $pkg.PackageFullName -AllUsers -ErrorAction Stop | Out-Null
+    $removedInstalled++
+  }} catch {{
+    $hasFailure = $true
+    Write-Output "[FAIL] $pkgName installed remove error: $($_.Exception.Message)"
+  }}
+}}
+
+try {{
+  $provisionedMatches = Get-AppxProvisionedPackage -Online -ErrorAction Stop | Where-Object {{ $_.DisplayName -eq $pkgName }}
+  foreach ($prov in $provisionedMatches) {{
+    try {{
+      Remove-AppxProvisionedPackage -Online -PackageName $prov.PackageName -ErrorAction Stop | Out-Null
+      $removedProvisioned++
+    }} catch {{
+      $hasFailure = $true
+      Write-Output "[FAIL] $pkgName provisioned remove error: $($_.Exception.Message)"
+    }}
+  }}
+}} catch {{
+  $hasFailure = $true
+  Write-Output "[FAIL] $pkgName provisioned query error: $($_.Exception.Message)"
+}}
+
+if ($removedInstalled -gt 0 -or $removedProvisioned -gt 0) {{
+  Write-Output "[OK] $pkgName removed installed=$removedInstalled provisioned=$removedProvisioned"
+}} elseif ($hasFailure) {{
+  Write-Output "[WARN] $pkgName no removal completed"
+}} else {{
+  Write-Output "[SKIP] $pkgName not installed"
+}}
+"#
+    );
+
+    match run_powershell(&script) {
+        Ok(script_output) => {
+            let clean_output = script_output.trim();
+            if clean_output.is_empty() {
+                (
+                    BloatwareOutcome::Skipped,
+                    vec![format!("[SKIP] {} no output returned", package_name)],
+                )
+            } else {
+                let lines = clean_output
+                    .lines()
+                    .map(|line| line.trim_end().to_string())
+                    .collect();
+                let outcome = if clean_output.contains("[FAIL]") {
+                    BloatwareOutcome::Failed
+                } else if clean_output.contains("[OK]") {
+                    BloatwareOutcome::Removed
+                } else {
+                    BloatwareOutcome::Skipped
+                };
+                (outcome, lines)
+            }
+        }
+        Err(err) => (
+            BloatwareOutcome::Failed,
+            vec![format!(
+                "[FAIL] {} command execution failed: {}",
+                package_name,
+                err.to_string().trim()
+            )],
+        ),
+    }
+}
+
+/// Every removable package as (package name, label, category, vendor)
+fn bloatware_catalog() -> Vec<(&'static str, &'static str, &'static str, Option<&'static str>)> {
+    BLOATWARE_CANDIDATES
+        .iter()
+        .map(|(package_name, label, category)| (*package_name, *label, *category, None))
+        .chain(
+            OEM_BLOATWARE
+                .iter()
+                .map(|(package_name, label, vendor)| (*package_name, *label, "OEM", Some(*vendor))),
+        )
+        .collect()
+}
+
+/// Parse `ConvertTo-Json` output, which is a bare object for a single row
+fn parse_json_rows(output: &str) -> Result<Vec<serde_json::Value>, NetworkError> {
+    if output.trim().is_empty() {
+        return Ok(vec![]);
+    }
+    let data: serde_json::Value = serde_json::from_str(output.trim())
+        .map_err(|e| NetworkError::parse(format!("JSON parse error: {}", e)))?;
+    Ok(match data {
+        serde_json::Value::Array(arr) => arr,
+        obj @ serde_json::Value::Object(_) => vec![obj],
+        _ => vec![],
+    })
+}
+
+/// PTR hostname for one IP, or an empty string when there is no record
+fn reverse_lookup(ip: &str) -> String {
+    let script = format!(
+        "try {{ [System.Net.Dns]::GetHostEntry('{}').HostName }} catch {{ '' }}",
+        ps_escape_single_quoted(ip)
+    );
+    let hostname = run_powershell_with_timeout(&script, Duration::from_secs(10))
+        .map(|out| out.trim().to_string())
+        .unwrap_or_default();
+    // GetHostEntry echoes the address back when there is no PTR record.
+    if hostname == ip {
+        String::new()
+    } else {
+        hostname
+    }
+}
+
+/// Normalize `aa:bb:cc:dd:ee:ff`, `AA-BB-...`, `aabb.ccdd.eeff` or bare hex to `AABBCCDDEEFF`
+fn normalize_mac(mac: &str) -> Option<String> {
+    let mut hex = String::with_capacity(12);
+    for ch in mac.trim().chars() {
+        match ch {
+            ':' | '-' | '.' | ' ' => {}
+            ch if ch.is_ascii_hexdigit() => hex.push(ch.to_ascii_uppercase()),
+            _ => return None,
+        }
+    }
+    (hex.len() == 12).then_some(hex)
+}
+
+/// `AABBCCDDEEFF` -> `AA-BB-CC-DD-EE-FF`
+fn dashed_mac(hex: &str) -> String {
+    hex.as_bytes()
+        .chunks(2)
+        .map(|pair| String::from_utf8_lossy(pair).into_owned())
+        .collect::<Vec<String>>()
+        .join("-")
+}
+
+/// Best-effort vendor for a MAC address from the embedded OUI table
+fn lookup_oui_vendor(mac: &str) -> Option<&'static str> {
+    let hex = normalize_mac(mac)?;
+    OUI_VENDORS
+        .iter()
+        .find(|(prefix, _)| hex.starts_with(prefix))
+        .map(|(_, vendor)| *vendor)
+}
+
+fn dns_record_type_name(code: &str) -> String {
+    match code {
+        "1" => "A",
+        "2" => "NS",
+        "5" => "CNAME",
+        "6" => "SOA",
+        "12" => "PTR",
+        "15" => "MX",
+        "16" => "TXT",
+        "28" => "AAAA",
+        "33" => "SRV",
+        other => other,
+    }
+    .to_string()
+}
+
+/// Parse `ipconfig /displaydns`. Each record is a run of six dotted "Key . . : value" lines
+/// (name, type, TTL, data length, section, data); matching by position keeps it locale-neutral.
+fn parse_displaydns(text: &str) -> Vec<DnsCacheEntry> {
+    let mut entries = Vec::new();
+    let mut fields: Vec<String> = Vec::new();
+
+    let mut flush = |fields: &mut Vec<String>| {
+        if fields.len() >= 6 {
+            entries.push(DnsCacheEntry {
+                name: fields[0].clone(),
+                record_type: dns_record_type_name(&fields[1]),
+                ttl: fields[2].parse().unwrap_or(0),
+                data: fields[5].clone(),
+            });
+        }
+        fields.clear();
+    };
+
+    for line in text.lines() {
+        match line.find(" : ") {
+            Some(pos) if line[..pos].contains(". .") || line[..pos].trim_end().ends_with('.') => {
+                fields.push(line[pos + 3..].trim().to_string());
+                if fields.len() == 6 {
+                    flush(&mut fields);
+                }
+            }
+            _ if line.trim().is_empty() => flush(&mut fields),
+            _ => {}
+        }
+    }
+    flush(&mut fields);
+    entries
+}
+
+/// Quote a CSV field when it contains a delimiter, quote or newline
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Serialize one section of a report, keeping failures as `{"error": ...}` instead of aborting
+fn report_section<T: Serialize>(result: Result<T, NetworkError>) -> serde_json::Value {
+    match result {
+        Ok(value) => serde_json::to_value(value)
+            .unwrap_or_else(|e| serde_json::json!({ "error": e.to_string() })),
+        Err(e) => serde_json::json!({ "error": e }),
+    }
+}
+
+/// Check that a requested ping source address is an IPv4 assigned to this machine
+fn validate_source_ip(source_ip: Option<String>) -> Result<Option<String>, NetworkError> {
+    let source = match source_ip
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+    {
+        Some(source) => source,
+        None => return Ok(None),
+    };
+    let source = source
+        .parse::<std::net::Ipv4Addr>()
+        .map_err(|_| NetworkError::invalid(format!("Invalid source IP: {}", source)))?
+        .to_string();
+    if !interface_ipv4_addresses(None).contains(&source) {
+        return Err(NetworkError::invalid(format!(
+            "Source IP {} is not assigned to any interface",
+            source
+        )));
+    }
+    Ok(Some(source))
+}
+
+/// One don't-fragment ping with an ICMP payload of `payload` bytes
+fn ping_dont_fragment(target: &str, payload: u32) -> bool {
+    let size = payload.to_string();
+    Command::new("ping")
+        .args(["-n", "1", "-w", "1000", "-f", "-l", &size, target])
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+        .map(|out| String::from_utf8_lossy(&out.stdout).contains("TTL="))
+        .unwrap_or(false)
+}
+
+/// `Get-NetNeighbor` for one address family ("IPv4"/"IPv6") with unreachable and
+/// all-zero entries dropped and MACs normalized to `AA-BB-CC-DD-EE-FF`
+fn read_neighbors(address_family: &str) -> Result<Vec<NeighborEntry>, NetworkError> {
+    let script = format!(
+        r#"
+        Get-NetNeighbor -AddressFamily {address_family} -ErrorAction SilentlyContinue |
+        Where-Object {{ $_.State -ne 'Unreachable' }} |
+        ForEach-Object {{
+            [PSCustomObject]@{{
+                IPAddress = $_.IPAddress
+                LinkLayerAddress = $_.LinkLayerAddress
+                InterfaceIndex = $_.InterfaceIndex
+                State = $_.State.ToString()
+            }}
+        }} |
+        ConvertTo-Json -Compress
+    "#
+    );
+
+    let neighbors = parse_json_rows(&run_powershell(&script)?)?
+        .iter()
+        .filter_map(|item| {
+            let hex = normalize_mac(item["LinkLayerAddress"].as_str().unwrap_or(""))?;
+            if hex.chars().all(|ch| ch == '0') {
+                return None;
+            }
+            let mac = dashed_mac(&hex);
+            Some(NeighborEntry {
+                ip: item["IPAddress"].as_str().unwrap_or("").to_string(),
+                mac,
+                interface_index: match &item["InterfaceIndex"] {
+                    serde_json::Value::Number(n) => n.to_string(),
+                    serde_json::Value::String(s) => s.clone(),
+                    _ => String::new(),
+                },
+                state: item["State"].as_str().unwrap_or("").to_string(),
+            })
+        })
+        .collect();
+    Ok(neighbors)
+}
+
+fn read_firewall_profiles() -> Result<Vec<FirewallProfile>, NetworkError> {
+    let ps_script = r#"
+        Get-NetFirewallProfile -ErrorAction Stop |
+        ForEach-Object {
+            [PSCustomObject]@{
+                Name = $_.Name
+                Enabled = ($_.Enabled.ToString() -eq 'True')
+            }
+        } |
+        ConvertTo-Json -Compress
+    "#;
+
+    let profiles = parse_json_rows(&run_powershell(ps_script)?)?
+        .iter()
+        .map(|item| FirewallProfile {
+            name: item["Name"].as_str().unwrap_or("").to_string(),
+            enabled: item["Enabled"].as_bool().unwrap_or(false),
+        })
+        .collect();
+    Ok(profiles)
+}
+
+/// `8-4-4-4-12` hex GUID without braces
+fn is_guid(value: &str) -> bool {
+    let groups: Vec<&str> = value.split('-').collect();
+    groups.len() == 5
+        && groups
+            .iter()
+            .zip([8, 4, 4, 4, 12])
+            .all(|(group, len)| group.len() == len && group.chars().all(|ch| ch.is_ascii_hexdigit()))
+}
+
+/// Parse `powercfg /list` lines like `Power Scheme GUID: <guid>  (Balanced) *`
+fn parse_power_plans(text: &str) -> Vec<PowerPlan> {
+    text.lines()
+        .filter_map(|line| {
+            let (_, rest) = line.split_once(": ")?;
+            let rest = rest.trim();
+            let guid = rest.split_whitespace().next()?;
+            if !is_guid(guid) {
+                return None;
+            }
+            let name = match (rest.find('('), rest.rfind(')')) {
+                (Some(open), Some(close)) if close > open => rest[open + 1..close].to_string(),
+                _ => String::new(),
+            };
+            Some(PowerPlan {
+                guid: guid.to_lowercase(),
+                name,
+                active: rest.ends_with('*'),
+            })
+        })
+        .collect()
+}
+
+fn validate_ipv4(value: &str, what: &str) -> Result<(), NetworkError> {
+    value
+        .trim()
+        .parse::<std::net::Ipv4Addr>()
+        .map(|_| ())
+        .map_err(|_| NetworkError::invalid(format!("Invalid {}: {}", what, value)))
+}
+
+fn validate_route_spec(spec: &RouteSpec) -> Result<(), NetworkError> {
+    validate_ipv4(&spec.destination, "route destination")?;
+    mask_to_prefix(&spec.mask)?;
+    validate_ipv4(&spec.gateway, "route gateway")?;
+    if spec.metric.trim().parse::<u32>().is_err() {
+        return Err(NetworkError::invalid(format!(
+            "Invalid route metric: {}",
+            spec.metric
+        )));
+    }
+    if let Some(idx) = spec.interface_index.as_deref().filter(|idx| !idx.trim().is_empty()) {
+        validate_interface_index(idx)?;
+    }
+    Ok(())
+}
+
+fn validate_interface_backup(backup: &InterfaceConfigBackup) -> Result<(), NetworkError> {
+    validate_interface_index(&backup.interface_index)?;
+    for address in &backup.addresses {
+        validate_ipv4(&address.ip, "address")?;
+        if !(1..=32).contains(&address.prefix_length) {
+            return Err(NetworkError::invalid(format!(
+                "Invalid prefix length: {}",
+                address.prefix_length
+            )));
+        }
+    }
+    if let Some(gateway) = &backup.gateway {
+        validate_ipv4(gateway, "gateway")?;
+    }
+    for server in &backup.dns_servers {
+        validate_ipv4(server, "DNS server")?;
+    }
+    if !backup.dhcp && backup.addresses.is_empty() {
+        return Err(NetworkError::invalid(format!(
+            "Interface {} is static but has no addresses",
+            backup.interface_index
+        )));
+    }
+    Ok(())
+}
+
+/// PowerShell that re-applies one interface's addressing and DNS (values pre-validated)
+fn interface_restore_script(backup: &InterfaceConfigBackup) -> String {
+    let idx = &backup.interface_index;
+    let mut lines = vec!["$ErrorActionPreference = 'Stop'".to_string()];
+    if backup.dhcp {
+        lines.push(format!("Set-NetIPInterface -InterfaceIndex {} -Dhcp Enabled", idx));
+    } else {
+        lines.push(format!(
+            "Remove-NetRoute -InterfaceIndex {} -DestinationPrefix 0.0.0.0/0 -Confirm:$false -ErrorAction SilentlyContinue",
+            idx
+        ));
+        lines.push(format!(
+            "Remove-NetIPAddress -InterfaceIndex {} -AddressFamily IPv4 -Confirm:$false -ErrorAction SilentlyContinue",
+            idx
+        ));
+        lines.push(format!("Set-NetIPInterface -InterfaceIndex {} -Dhcp Disabled", idx));
+        for (i, address) in backup.addresses.iter().enumerate() {
+            let gateway = match (&backup.gateway, i) {
+                (Some(gw), 0) => format!(" -DefaultGateway {}", gw),
+                _ => String::new(),
+            };
+            lines.push(format!(
+                "New-NetIPAddress -InterfaceIndex {} -IPAddress {} -PrefixLength {}{} | Out-Null",
+                idx, address.ip, address.prefix_length, gateway
+            ));
+        }
+    }
+    if backup.dns_static && !backup.dns_servers.is_empty() {
+        let servers = backup
+            .dns_servers
+            .iter()
+            .map(|s| format!("'{}'", s))
+            .collect::<Vec<String>>()
+            .join(",");
+        lines.push(format!(
+            "Set-DnsClientServerAddress -InterfaceIndex {} -ServerAddresses {}",
+            idx, servers
+        ));
+    } else {
+        lines.push(format!(
+            "Set-DnsClientServerAddress -InterfaceIndex {} -ResetServerAddresses",
+            idx
+        ));
+    }
+    lines.join("\n")
+}
+
+/// `Find-NetRoute` for one destination: the winning route plus source address and interface
+fn find_route(destination: std::net::Ipv4Addr) -> Result<RouteLookup, NetworkError> {
+    let script = format!(
+        r#"
+        $found = Find-NetRoute -RemoteIPAddress '{destination}' -ErrorAction Stop
+        $address = $found | Where-Object {{ $_.CimClass.CimClassName -eq 'MSFT_NetIPAddress' }} | Select-Object -First 1
+        $route = $found | Where-Object {{ $_.CimClass.CimClassName -eq 'MSFT_NetRoute' }} | Select-Object -First 1
+        [PSCustomObject]@{{
+            DestinationPrefix = $route.DestinationPrefix
+            NextHop = $route.NextHop
+            RouteMetric = $route.RouteMetric
+            InterfaceIndex = $route.InterfaceIndex
+            SourceAddress = $address.IPAddress
+            InterfaceAlias = $address.InterfaceAlias
+        }} | ConvertTo-Json -Compress
+    "#
+    );
+
+    let item = parse_json_rows(&run_powershell(&script)?)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| NetworkError::not_found(format!("No route to {}", destination)))?;
+    let route = route_entry_from_json(&item);
+    Ok(RouteLookup {
+        interface_index: route.interface_index.clone(),
+        route,
+        source_ip: item["SourceAddress"].as_str().unwrap_or("").to_string(),
+        interface_alias: item["InterfaceAlias"].as_str().unwrap_or("").to_string(),
+    })
+}
+
+/// Parse `tracert -d` hop lines like `  2    10 ms    <1 ms     *     10.0.0.1`
+fn parse_tracert(text: &str) -> Vec<TraceHop> {
+    let mut hops = Vec::new();
+    for line in text.lines() {
+        let mut tokens = line.split_whitespace().peekable();
+        let hop = match tokens.next().and_then(|t| t.parse::<u32>().ok()) {
+            Some(hop) => hop,
+            None => continue,
+        };
+
+        let mut latencies_ms = Vec::new();
+        for _ in 0..3 {
+            match tokens.next() {
+                Some("*") => latencies_ms.push(None),
+                Some(value) => {
+                    // "<1 ms" rounds up to 1 like ping's "time<1ms"
+                    let value = value.trim_start_matches('<');
+                    latencies_ms.push(value.parse::<u32>().ok().map(|ms| ms.max(1)));
+                    if tokens.peek() == Some(&"ms") {
+                        tokens.next();
+                    }
+                }
+                None => break,
+            }
+        }
+        if latencies_ms.len() != 3 {
+            continue;
+        }
+
+        let address = tokens
+            .last()
+            .filter(|last| last.parse::<std::net::IpAddr>().is_ok())
+            .unwrap_or("")
+            .to_string();
+        hops.push(TraceHop {
+            hop,
+            address,
+            latencies_ms,
+        });
+    }
+    hops
+}
+
+fn run_traceroute(target: &str, max_hops: u32) -> Result<Vec<TraceHop>, NetworkError> {
+    let max_hops = max_hops.to_string();
+    let output = run_cmd_with_timeout(
+        "tracert",
+        &["-d", "-h", &max_hops, "-w", "1000", target],
+        Duration::from_secs(180),
+    )?;
+    Ok(parse_tracert(&output))
+}
+
+/// Extract the "Persistent Routes:" section of `route print -4`. Rows are
+/// `Network Address  Netmask  Gateway Address  Metric`; the section holds a single
+/// "None" line when nothing is persisted. route.exe does not print an interface
+/// column here, so `interface_index` stays empty.
+fn parse_persistent_routes(output: &str) -> Vec<RouteEntry> {
+    let mut routes = Vec::new();
+    let mut in_section = false;
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if !in_section {
+            in_section = trimmed.eq_ignore_ascii_case("Persistent Routes:");
+            continue;
+        }
+        if trimmed.starts_with("===") {
+            break;
+        }
+        let fields: Vec<&str> = trimmed.split_whitespace().collect();
+        if fields.len() < 4
+            || fields[0].parse::<std::net::Ipv4Addr>().is_err()
+            || fields[1].parse::<std::net::Ipv4Addr>().is_err()
+        {
+            // Column header or "None"
+            continue;
+        }
+        routes.push(RouteEntry {
+            destination: fields[0].to_string(),
+            netmask: fields[1].to_string(),
+            gateway: fields[2].to_string(),
+            metric: fields[3].to_string(),
+            interface_index: String::new(),
+        });
+    }
+    routes
+}
+
+/// Poll the adapter status until it reports Up or `timeout` elapses
+fn wait_for_adapter_up(interface_index: &str, timeout: Duration) -> bool {
+    let script = format!(
+        "(Get-NetAdapter -InterfaceIndex {} -ErrorAction SilentlyContinue).Status",
+        interface_index
+    );
+    let deadline = Instant::now() + timeout;
+    loop {
+        if let Ok(status) = run_powershell(&script) {
+            if status.trim().eq_ignore_ascii_case("Up") {
+                return true;
+            }
+        }
+        if Instant::now() >= deadline {
+            return false;
+        }
+        thread::sleep(Duration::from_millis(500));
+    }
+}
+
+/// Whether the IPv4 table now holds `spec`'s destination/mask/gateway. Addresses are
+/// compared parsed, so "010.0.0.1" style input still matches.
+fn route_in_table(spec: &RouteSpec) -> Result<bool, NetworkError> {
+    let same = |a: &str, b: &str| match (
+        a.trim().parse::<std::net::Ipv4Addr>(),
+        b.trim().parse::<std::net::Ipv4Addr>(),
+    ) {
+        (Ok(x), Ok(y)) => x == y,
+        _ => a.trim() == b.trim(),
+    };
+    Ok(read_routing_table(None)?.iter().any(|route| {
+        same(&route.destination, &spec.destination)
+            && same(&route.netmask, &spec.mask)
+            && same(&route.gateway, &spec.gateway)
+    }))
+}
+
+/// Network/broadcast/host range for `ip` with a dotted mask or a CIDR prefix
+/// ("24" or "/24"). /31 and /32 follow RFC 3021: every address is usable.
+fn subnet_info(ip: &str, mask: &str) -> Result<SubnetInfo, NetworkError> {
+    let addr = u32::from(
+        ip.trim()
+            .parse::<std::net::Ipv4Addr>()
+            .map_err(|_| NetworkError::invalid(format!("Invalid IP address: {}", ip)))?,
+    );
+    let mask = mask.trim();
+    let prefix = if mask.contains('.') {
+        mask_to_prefix(mask)?
+    } else {
+        match mask.trim_start_matches('/').parse::<u32>() {
+            Ok(prefix) if prefix <= 32 => prefix,
+            _ => {
+                return Err(NetworkError::invalid(format!(
+                    "Invalid prefix length: {}",
+                    mask
+                )))
+            }
+        }
+    };
+
+    let mask_bits = u32::MAX.checked_shl(32 - prefix).unwrap_or(0);
+    let network = addr & mask_bits;
+    let broadcast = network | !mask_bits;
+    let (first_host, last_host, usable_hosts) = if prefix >= 31 {
+        (network, broadcast, broadcast - network + 1)
+    } else {
+        (network + 1, broadcast - 1, broadcast - network - 1)
+    };
+    let dotted = |bits: u32| std::net::Ipv4Addr::from(bits).to_string();
+
+    Ok(SubnetInfo {
+        network: dotted(network),
+        broadcast: dotted(broadcast),
+        first_host: dotted(first_host),
+        last_host: dotted(last_host),
+        usable_hosts,
+        prefix_length: prefix,
+    })
+}
+
+/// Saved preferences, or the defaults when the file is missing or unreadable
+fn load_preferences(app: &tauri::AppHandle) -> Preferences {
+    app_data_dir(app)
+        .ok()
+        .and_then(|dir| fs::read_to_string(dir.join(PREFERENCES_FILE)).ok())
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// Dynamic (non-permanent) IPv4 neighbor entries, optionally for one interface
+fn count_arp_entries(interface_index: Option<&str>) -> Option<u32> {
+    let filter = interface_index
+        .map(|idx| format!(" -InterfaceIndex {}", idx))
+        .unwrap_or_default();
+    let script = format!(
+        "@(Get-NetNeighbor -AddressFamily IPv4{} -ErrorAction SilentlyContinue | Where-Object {{ $_.State -ne 'Permanent' }}).Count",
+        filter
+    );
+    run_powershell(&script).ok()?.trim().parse().ok()
+}
+
+fn delete_temporary_route(route: &TemporaryRoute) -> Result<String, NetworkError> {
+    run_cmd(
+        "route",
+        &["delete", &route.destination, "mask", &route.mask, &route.gateway],
+    )
+}
+
+/// Delete every route still waiting to expire. Called when the app exits so
+/// test routes never outlive the session that created them.
+pub fn remove_temporary_routes() {
+    let routes: Vec<TempRouteEntry> = {
+        let mut registry = match TEMP_ROUTES.lock() {
+            Ok(g) => g,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        registry.drain().map(|(_, entry)| entry).collect()
+    };
+    for (route, flag) in routes {
+        flag.store(true, Ordering::Relaxed);
+        let _ = delete_temporary_route(&route);
+    }
+}
+
+/// One A-record lookup of `domain` (optionally against a specific `server`),
+/// returning the lookup time in ms. Timed inside PowerShell so process startup
+/// does not skew the numbers. Callers validate `domain`/`server` first.
+fn timed_dns_lookup(domain: &str, server: Option<&str>) -> Result<u32, NetworkError> {
+    let server_arg = server
+        .map(|s| format!(" -Server '{}'", s))
+        .unwrap_or_default();
+    let script = format!(
+        r#"
+        $sw = [Diagnostics.Stopwatch]::StartNew()
+        try {{
+            Resolve-DnsName -Name '{domain}'{server_arg} -Type A -DnsOnly -NoHostsFile -QuickTimeout -ErrorAction Stop | Out-Null
+            $ok = $true
+            $msg = ''
+        }} catch {{ $ok = $false; $msg = $_.Exception.Message }}
+        $sw.Stop()
+        "$ok|$($sw.ElapsedMilliseconds)|$msg"
+    "#
+    );
+    let output = run_powershell_with_timeout(&script, Duration::from_secs(15))?;
+    let mut parts = output.trim().splitn(3, '|');
+    let ok = parts.next().unwrap_or("");
+    let elapsed = parts.next().unwrap_or("0").parse().unwrap_or(0);
+    if ok.eq_ignore_ascii_case("true") {
+        Ok(elapsed)
+    } else {
+        let message = parts.next().unwrap_or("").trim();
+        Err(NetworkError::command_failed(
+            "Resolve-DnsName",
+            if message.is_empty() {
+                format!("Lookup of {} failed", domain)
+            } else {
+                message.to_string()
+            },
+        ))
+    }
+}
+
+fn read_interface_metric(interface_index: &str) -> Result<InterfaceMetric, NetworkError> {
+    let script = format!(
+        "Get-NetIPInterface -InterfaceIndex {} -AddressFamily IPv4 -ErrorAction Stop | \
+         Select-Object InterfaceMetric, @{{n='AutomaticMetric';e={{$_.AutomaticMetric.ToString()}}}} | \
+         ConvertTo-Json -Compress",
+        interface_index
+    );
+    let row = parse_json_rows(&run_powershell(&script)?)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| {
+            NetworkError::not_found(format!("No IPv4 interface with index {}", interface_index))
+        })?;
+    Ok(InterfaceMetric {
+        interface_index: interface_index.to_string(),
+        metric: row["InterfaceMetric"].as_u64().unwrap_or(0) as u32,
+        automatic: row["AutomaticMetric"].as_str() == Some("Enabled"),
+    })
+}
+
+// ======================== TAURI COMMANDS ========================
+
+/// Get list of active network interfaces (NICs). With `probe_gateways` each
+/// gateway is pinged once (in parallel) to fill `gateway_latency_ms`.
+#[tauri::command]
+pub async fn get_network_interfaces(
+    active_only: bool,
+    probe_gateways: Option<bool>,
+) -> Result<Vec<NetworkInterface>, NetworkError> {
+    let ps_script = r#"
+        Get-WmiObject Win32_NetworkAdapterConfiguration |
+        Where-Object { $_.InterfaceIndex -ne $null } |
+        Select-Object InterfaceIndex, Description, IPAddress, DefaultIPGateway |
+        ConvertTo-Json -Compress
+    "#;
+
+    let output = run_powershell(ps_script)?;
+    let data: serde_json::Value = serde_json::from_str(&output)
+        .map_err(|e| NetworkError::parse(format!("JSON parse error: {}", e)))?;
+
+    let items = match &data {
+        serde_json::Value::Array(arr) => arr.clone(),
+        obj @ serde_json::Value::Object(_) => vec![obj.clone()],
+        _ => return Ok(vec![]),
+    };
+
+    let blacklist = [
+        "virtual", "vmware", "vbox", "loopback", "wintun", "kernel",
+        "miniport", "wi-fi direct", "tap-", "pseudo", "ethernet adapter v",
+    ];
+
+    let mut interfaces: Vec<NetworkInterface> = Vec::new();
+
+    for item in &items {
+        let desc = item["Description"].as_str().unwrap_or("").to_string();
+        let desc_lower = desc.to_lowercase();
+
+        if blacklist.iter().any(|b| desc_lower.contains(b)) {
+            continue;
+        }
+
+        let ip = match &item["IPAddress"] {
+            serde_json::Value::Array(arr) => {
+                arr.first()
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("0.0.0.0")
+                    .to_string()
+            }
+            serde_json::Value::String(s) => s.clone(),
+            _ => "0.0.0.0".to_string(),
+        };
+
+        if active_only && (ip.is_empty() || ip == "0.0.0.0") {
+            continue;
+        }
 
         let gateway = match &item["DefaultIPGateway"] {
             serde_json::Value::Array(arr) => {
@@ -400,677 +3046,4373 @@ pub async fn get_network_interfaces(active_only: bool) -> Result<Vec<NetworkInte
                     .unwrap_or("")
                     .to_string()
             }
-            serde_json::Value::String(s) => s.clone(),
-            _ => String::new(),
+            serde_json::Value::String(s) => s.clone(),
+            _ => String::new(),
+        };
+
+        let index = match &item["InterfaceIndex"] {
+            serde_json::Value::Number(n) => n.to_string(),
+            serde_json::Value::String(s) => s.clone(),
+            _ => String::new(),
+        };
+
+        interfaces.push(NetworkInterface {
+            index,
+            ip,
+            gateway,
+            description: desc,
+            gateway_latency_ms: None,
+        });
+    }
+
+    if probe_gateways.unwrap_or(false) {
+        let gateways: Vec<String> = interfaces.iter().map(|iface| iface.gateway.clone()).collect();
+        let latencies = run_ordered_pool(gateways, 8, |gateway| {
+            if gateway.is_empty() {
+                return None;
+            }
+            let result = ping_once_target(gateway, "1000", 1);
+            result.success.then_some(result.latency_ms)
+        });
+        for (iface, latency) in interfaces.iter_mut().zip(latencies) {
+            iface.gateway_latency_ms = latency;
+        }
+    }
+
+    Ok(interfaces)
+}
+
+/// Get IPv4 routing table, optionally only the routes of one interface
+#[tauri::command]
+pub async fn get_routing_table(
+    interface_index: Option<String>,
+) -> Result<Vec<RouteEntry>, NetworkError> {
+    read_routing_table(interface_index.as_deref())
+}
+
+/// Add a persistent route, then confirm it actually shows up in the routing table
+#[tauri::command]
+pub async fn add_route(
+    app: tauri::AppHandle,
+    destination: String,
+    mask: String,
+    gateway: String,
+    metric: String,
+    interface_index: Option<String>,
+) -> Result<CommandResult, NetworkError> {
+    require_admin("Adding a route")?;
+
+    let spec = RouteSpec {
+        destination,
+        mask,
+        gateway,
+        metric,
+        interface_index,
+    };
+    let result = add_route_entry(&spec);
+    log_route_mutation(&app, "add_route", serde_json::json!(spec), &result);
+    let mut output = result?;
+
+    // route.exe exits 0 for some routes Windows then drops (e.g. an off-link gateway)
+    let success = match route_in_table(&spec) {
+        Ok(true) => true,
+        Ok(false) => {
+            output.push_str("\n[FAIL] route command succeeded but route not found in table");
+            false
+        }
+        Err(e) => {
+            output.push_str(&format!("\n[SKIP] Could not verify route: {}", e));
+            true
+        }
+    };
+
+    Ok(CommandResult { success, output })
+}
+
+/// Delete a route
+#[tauri::command]
+pub async fn delete_route(
+    app: tauri::AppHandle,
+    destination: String,
+    mask: String,
+) -> Result<CommandResult, NetworkError> {
+    require_admin("Deleting a route")?;
+
+    let result = run_cmd("route", &["delete", &destination, "mask", &mask]);
+    log_route_mutation(
+        &app,
+        "delete_route",
+        serde_json::json!({ "destination": destination, "mask": mask }),
+        &result,
+    );
+    let result = result?;
+    Ok(CommandResult {
+        success: true,
+        output: result,
+    })
+}
+
+/// Flush all routes
+#[tauri::command]
+pub async fn flush_routes(app: tauri::AppHandle) -> Result<CommandResult, NetworkError> {
+    require_admin("Flushing routes")?;
+
+    let result = run_cmd("route", &["-f"]);
+    log_route_mutation(&app, "flush_routes", serde_json::json!({}), &result);
+    let result = result?;
+    Ok(CommandResult {
+        success: true,
+        output: result,
+    })
+}
+
+/// Set a NIC as default internet gateway
+#[tauri::command]
+pub async fn set_default_gateway(
+    app: tauri::AppHandle,
+    gateway: String,
+    interface_index: String,
+) -> Result<CommandResult, NetworkError> {
+    require_admin("Changing the default gateway")?;
+    let interface_index = validate_interface_index(&interface_index)?;
+
+    // Raise metric of all existing default routes
+    let _ = run_powershell("Set-NetRoute -DestinationPrefix 0.0.0.0/0 -RouteMetric 500");
+
+    // Delete existing default route
+    let _ = run_cmd("route", &["delete", "0.0.0.0"]);
+
+    // Add new default route with low metric
+    let result = run_cmd(
+        "route",
+        &[
+            "-p",
+            "add",
+            "0.0.0.0",
+            "mask",
+            "0.0.0.0",
+            &gateway,
+            "metric",
+            "1",
+            "if",
+            &interface_index,
+        ],
+    );
+    log_route_mutation(
+        &app,
+        "set_default_gateway",
+        serde_json::json!({ "gateway": gateway, "interface_index": interface_index }),
+        &result,
+    );
+    let result = result?;
+
+    Ok(CommandResult {
+        success: true,
+        output: result,
+    })
+}
+
+/// Run a network fix command (flush DNS, renew IP, etc.)
+#[tauri::command]
+pub async fn run_network_command(command: String) -> Result<CommandResult, NetworkError> {
+    run_whitelisted_command(&command)
+}
+
+/// Run several whitelisted commands in order. A rejected or unstartable command
+/// becomes a failed entry instead of aborting the rest of the batch.
+#[tauri::command]
+pub async fn run_network_commands(commands: Vec<String>) -> Vec<CommandResult> {
+    commands
+        .iter()
+        .map(|command| {
+            run_whitelisted_command(command).unwrap_or_else(|e| CommandResult {
+                success: false,
+                output: e.to_string(),
+            })
+        })
+        .collect()
+}
+
+fn run_whitelisted_command(command: &str) -> Result<CommandResult, NetworkError> {
+    let cmd_lower = command.to_lowercase();
+    if !ALLOWED_COMMAND_PREFIXES
+        .iter()
+        .any(|prefix| cmd_lower.starts_with(prefix))
+    {
+        return Err(NetworkError::invalid("Command not allowed"));
+    }
+
+    let output = Command::new("cmd")
+        .args(["/C", command])
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+        .map_err(|e| {
+            NetworkError::command_failed("command", format!("Failed to run command: {}", e))
+        })?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+
+    Ok(CommandResult {
+        success: output.status.success(),
+        output: if stdout.is_empty() { stderr } else { stdout },
+    })
+}
+
+/// Ping a host and return latency, optionally from a specific local source address.
+/// `timeout_ms` is the per-reply wait (default from preferences, clamped to 200..=15000).
+#[tauri::command]
+pub async fn ping_host(
+    app: tauri::AppHandle,
+    target: String,
+    count: Option<u32>,
+    source_ip: Option<String>,
+    timeout_ms: Option<u32>,
+) -> Result<PingResult, NetworkError> {
+    let n = count.unwrap_or(1).to_string();
+    let timeout = timeout_ms
+        .unwrap_or_else(|| load_preferences(&app).ping_timeout_ms)
+        .clamp(200, 15_000)
+        .to_string();
+    let source_ip = validate_source_ip(source_ip)?;
+    let mut args = vec!["-n", &n, "-w", &timeout];
+    if let Some(ref source) = source_ip {
+        args.extend(["-S", source]);
+    }
+    args.push(&target);
+
+    let start = Instant::now();
+    let output = Command::new("ping")
+        .args(&args)
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+        .map_err(|e| NetworkError::command_failed("ping", format!("Ping failed: {}", e)))?;
+
+    let elapsed = start.elapsed().as_millis() as u32;
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+
+    let (latency, _) = parse_ping_latency(&stdout, elapsed);
+
+    Ok(PingResult {
+        success: stdout.contains("Reply from") || stdout.contains("time="),
+        latency_ms: latency,
+        output: stdout,
+    })
+}
+
+/// Get bloatware candidates and installation status
+#[tauri::command]
+pub async fn get_bloatware_candidates() -> Result<Vec<BloatwareItem>, NetworkError> {
+    let ps_script = r#"
+        $names = @()
+        try {
+            $names += Get-AppxPackage -AllUsers -ErrorAction Stop | Select-Object -ExpandProperty Name
+        } catch {
+            $names += Get-AppxPackage -ErrorAction SilentlyContinue | Select-Object -ExpandProperty Name
+        }
+        try {
+            $names += Get-AppxProvisionedPackage -Online -ErrorAction Stop | Select-Object -ExpandProperty DisplayName
+        } catch {}
+        $names |
+            Where-Object { $_ -and $_.Trim().Length -gt 0 } |
+            ForEach-Object { $_.ToLowerInvariant() } |
+            Sort-Object -Unique |
+            ConvertTo-Json -Compress
+    "#;
+
+    let output = run_powershell(ps_script)?;
+    let mut installed = HashSet::new();
+    let parsed = serde_json::from_str::<serde_json::Value>(output.trim())
+        .unwrap_or(serde_json::Value::Array(vec![]));
+
+    match parsed {
+        serde_json::Value::Array(arr) => {
+            for item in arr {
+                if let Some(name) = item.as_str() {
+                    installed.insert(name.to_lowercase());
+                }
+            }
+        }
+        serde_json::Value::String(single_name) => {
+            installed.insert(single_name.to_lowercase());
+        }
+        _ => {}
+    }
+
+    let mut items: Vec<BloatwareItem> = bloatware_catalog()
+        .into_iter()
+        .map(|(package_name, label, category, vendor)| BloatwareItem {
+            package_name: package_name.to_string(),
+            label: label.to_string(),
+            category: category.to_string(),
+            vendor: vendor.map(str::to_string),
+            installed: installed.contains(&package_name.to_lowercase()),
+        })
+        .collect();
+    items.sort_by(|a, b| a.category.cmp(&b.category).then_with(|| a.label.cmp(&b.label)));
+    Ok(items)
+}
+
+/// Remove selected bloatware packages, emitting `bloatware-progress` per package
+#[tauri::command]
+pub async fn remove_bloatware(
+    window: tauri::Window,
+    packages: Vec<String>,
+) -> Result<CommandResult, NetworkError> {
+    require_admin("Removing bloatware")?;
+
+    if packages.is_empty() {
+        return Err(NetworkError::invalid("No packages selected"));
+    }
+
+    let allowed: HashMap<String, &str> = bloatware_catalog()
+        .into_iter()
+        .map(|(package_name, _, _, _)| (package_name.to_lowercase(), package_name))
+        .collect();
+
+    let mut selected = Vec::new();
+    let mut seen = HashSet::new();
+    for raw in packages {
+        let trimmed = raw.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        let lower = trimmed.to_lowercase();
+        let is_safe_token = lower
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '.' || ch == '_' || ch == '-');
+        if !is_safe_token {
+            continue;
+        }
+
+        if let Some(canonical) = allowed.get(&lower) {
+            let canonical_name = (*canonical).to_string();
+            if seen.insert(canonical_name.clone()) {
+                selected.push(canonical_name);
+            }
+        }
+    }
+
+    if selected.is_empty() {
+        return Err(NetworkError::invalid(
+            "No valid bloatware packages selected",
+        ));
+    }
+
+    let mut output_lines = vec![
+        format!("Requested removal for {} package(s).", selected.len()),
+        "Administrative privileges may be required for removal.".to_string(),
+        String::new(),
+    ];
+    let mut removed = 0u32;
+    let mut skipped = 0u32;
+    let mut failed = 0u32;
+
+    let labels: HashMap<&str, &str> = bloatware_catalog()
+        .into_iter()
+        .map(|(package_name, label, _, _)| (package_name, label))
+        .collect();
+    let total = selected.len() as u32;
+    let jobs: Vec<(u32, String)> = selected
+        .into_iter()
+        .enumerate()
+        .map(|(index, name)| (index as u32 + 1, name))
+        .collect();
+
+    // AppX deployment serializes internally, so a few workers is the sweet spot.
+    let results = run_ordered_pool(jobs, 4, |(index, package_name)| {
+        let (outcome, lines) = remove_bloatware_package(&package_name);
+        let status = match outcome {
+            BloatwareOutcome::Removed => "removed",
+            BloatwareOutcome::Skipped => "skipped",
+            BloatwareOutcome::Failed => "failed",
+        };
+        let _ = window.emit(
+            "bloatware-progress",
+            BloatwareProgress {
+                label: labels
+                    .get(package_name.as_str())
+                    .copied()
+                    .unwrap_or(package_name.as_str())
+                    .to_string(),
+                package: package_name.clone(),
+                index,
+                total,
+                status: status.to_string(),
+            },
+        );
+        (outcome, lines)
+    });
+
+    for (outcome, lines) in results {
+        match outcome {
+            BloatwareOutcome::Removed => removed += 1,
+            BloatwareOutcome::Skipped => skipped += 1,
+            BloatwareOutcome::Failed => failed += 1,
+        }
+        output_lines.extend(lines);
+        output_lines.push(String::new());
+    }
+
+    output_lines.push(format!(
+        "Summary: removed={} skipped={} failed={}",
+        removed, skipped, failed
+    ));
+
+    Ok(CommandResult {
+        success: failed == 0,
+        output: output_lines.join("\n"),
+    })
+}
+
+/// Remove every installed bloatware candidate in one category (e.g. "Gaming")
+#[tauri::command]
+pub async fn remove_bloatware_category(
+    window: tauri::Window,
+    category: String,
+) -> Result<CommandResult, NetworkError> {
+    require_admin("Removing bloatware")?;
+
+    let category = category.trim().to_string();
+    let known = bloatware_catalog()
+        .iter()
+        .any(|(_, _, item_category, _)| item_category.eq_ignore_ascii_case(&category));
+    if !known {
+        return Err(NetworkError::invalid(format!(
+            "Unknown bloatware category: {}",
+            category
+        )));
+    }
+
+    let packages: Vec<String> = get_bloatware_candidates()
+        .await?
+        .into_iter()
+        .filter(|item| item.installed && item.category.eq_ignore_ascii_case(&category))
+        .map(|item| item.package_name)
+        .collect();
+
+    if packages.is_empty() {
+        return Ok(CommandResult {
+            success: true,
+            output: format!("[SKIP] No installed packages in category {}", category),
+        });
+    }
+
+    remove_bloatware(window, packages).await
+}
+
+/// Generate and return battery report HTML for in-app preview
+#[tauri::command]
+pub async fn get_battery_report() -> Result<BatteryReportResult, NetworkError> {
+    let report_path = std::env::temp_dir().join("SuperRoutePro-BatteryReport.html");
+    let report_path_arg = report_path.to_string_lossy().to_string();
+
+    let _ = run_cmd(
+        "powercfg",
+        &["/batteryreport", "/output", &report_path_arg],
+    )?;
+
+    let html = fs::read_to_string(&report_path).map_err(|e| {
+        NetworkError::io(format!(
+            "Failed to read battery report file: {} ({})",
+            report_path_arg, e
+        ))
+    })?;
+
+    if html.trim().is_empty() {
+        return Err(NetworkError::parse("Battery report is empty"));
+    }
+
+    Ok(BatteryReportResult { html })
+}
+
+/// Clear selected system/browser cache targets
+#[tauri::command]
+pub async fn clear_cache_targets(
+    window: tauri::Window,
+    targets: Vec<String>,
+) -> Result<CommandResult, NetworkError> {
+    require_admin("Cleaning caches")?;
+
+    if targets.is_empty() {
+        return Err(NetworkError::invalid("No cache targets selected"));
+    }
+
+    let mut selected: Vec<(String, &'static str, String)> = Vec::new();
+    let mut seen = HashSet::new();
+
+    for target in targets {
+        let trimmed = target.trim().to_lowercase();
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        let is_safe_token = trimmed
+            .chars()
+            .all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '_' || ch == '-');
+        if !is_safe_token {
+            continue;
+        }
+
+        if seen.contains(&trimmed) {
+            continue;
+        }
+
+        if let Some(recipe) = cache_cleanup_recipe(&trimmed) {
+            seen.insert(trimmed.clone());
+            selected.push((trimmed, recipe.label, recipe.script()));
+        }
+    }
+
+    if selected.is_empty() {
+        return Err(NetworkError::invalid("No valid cache targets selected"));
+    }
+
+    let mut output_lines = vec![
+        format!("Requested cleanup for {} cache target(s).", selected.len()),
+        String::new(),
+    ];
+    let mut success_count = 0u32;
+    let mut failed_count = 0u32;
+    let mut cancelled = false;
+    let mut services_touched = false;
+    let total = selected.len() as u32;
+    CACHE_CLEANUP_CANCEL.store(false, Ordering::Relaxed);
+
+    for (index, (target, label, script)) in selected.into_iter().enumerate() {
+        if CACHE_CLEANUP_CANCEL.load(Ordering::Relaxed) {
+            cancelled = true;
+            output_lines.push(format!(
+                "[SKIP] Cleanup cancelled; {} of {} target(s) not processed.",
+                total - index as u32,
+                total
+            ));
+            output_lines.push(String::new());
+            break;
+        }
+        output_lines.push(format!("[TARGET] {}", label));
+        let failed_before = failed_count;
+        services_touched |= SERVICE_CACHE_TARGETS.contains(&target.as_str());
+        let run_result = if SERVICE_CACHE_TARGETS.contains(&target.as_str()) {
+            run_powershell_retry(&script, 3, Duration::from_millis(1500))
+        } else {
+            run_powershell(&script)
+        };
+        match run_result {
+            Ok(raw_output) => {
+                let clean_output = raw_output.trim();
+                if clean_output.is_empty() {
+                    output_lines.push(format!("[OK] {} cleaned.", label));
+                    success_count += 1;
+                } else {
+                    output_lines.extend(clean_output.lines().map(|line| line.trim_end().to_string()));
+                    if clean_output.contains("[FAIL]") {
+                        failed_count += 1;
+                    } else {
+                        success_count += 1;
+                    }
+                }
+            }
+            Err(err) => {
+                failed_count += 1;
+                output_lines.push(format!(
+                    "[FAIL] {} cleanup error: {}",
+                    label,
+                    err.to_string().trim()
+                ));
+            }
+        }
+        output_lines.push(String::new());
+        let _ = window.emit(
+            "cache-progress",
+            CacheProgress {
+                target,
+                label: label.to_string(),
+                index: index as u32 + 1,
+                total,
+                status: if failed_count > failed_before {
+                    "failed"
+                } else {
+                    "cleaned"
+                }
+                .to_string(),
+            },
+        );
+    }
+
+    if cancelled && services_touched {
+        // A cancelled run must never leave Windows Update stopped
+        let restart = run_powershell(
+            "Start-Service -Name wuauserv -ErrorAction SilentlyContinue; Start-Service -Name bits -ErrorAction SilentlyContinue",
+        );
+        output_lines.push(match restart {
+            Ok(_) => "[OK] Windows Update services restarted.".to_string(),
+            Err(err) => format!(
+                "[FAIL] Could not restart Windows Update services: {}",
+                err.to_string().trim()
+            ),
+        });
+        output_lines.push(String::new());
+    }
+
+    output_lines.push(format!(
+        "Summary: success={} failed={}{}",
+        success_count,
+        failed_count,
+        if cancelled {
+            " (cancelled partway)"
+        } else {
+            ""
+        }
+    ));
+
+    Ok(CommandResult {
+        success: failed_count == 0 && !cancelled,
+        output: output_lines.join("\n"),
+    })
+}
+
+/// fping-like scan over multiple targets (parallel ping, `count` packets per host).
+/// `max_parallel` (1..=256, else the saved preference) overrides the CPU-based worker heuristic.
+/// `sort_by` reorders `hosts` by "latency" (dead hosts last), "target" or "status".
+/// `source_ip` forces pings out of the interface owning that local address.
+/// `stop_after` ends the scan once that many hosts have answered.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn fping_scan(
+    app: tauri::AppHandle,
+    targets: Vec<String>,
+    timeout_ms: Option<u32>,
+    scan_id: Option<String>,
+    count: Option<u32>,
+    max_parallel: Option<usize>,
+    sort_by: Option<String>,
+    source_ip: Option<String>,
+    stop_after: Option<u32>,
+) -> Result<FpingScanResult, NetworkError> {
+    let source_ip = validate_source_ip(source_ip)?;
+    let timeout = timeout_ms.unwrap_or(1200).clamp(200, 10_000).to_string();
+    let packet_count = count.unwrap_or(1).clamp(1, 100);
+    let sort_key = match sort_by.as_deref().map(|s| s.trim().to_lowercase()) {
+        None => None,
+        Some(key) if key.is_empty() => None,
+        Some(key) if matches!(key.as_str(), "latency" | "target" | "status") => Some(key),
+        Some(key) => {
+            return Err(NetworkError::invalid(format!(
+                "Invalid sort_by value: {}",
+                key
+            )))
+        }
+    };
+
+    let clean_targets: Vec<String> = targets
+        .into_iter()
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty())
+        .take(512)
+        .collect();
+
+    if clean_targets.is_empty() {
+        return Err(NetworkError::invalid("No targets provided"));
+    }
+
+    let worker_count = match max_parallel.or_else(|| load_preferences(&app).fping_max_parallel) {
+        // Pings are I/O-bound, so an explicit request is not capped by CPU count.
+        Some(requested) => clean_targets.len().min(requested.clamp(1, 256)),
+        None => {
+            let cpu_workers = thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(4);
+            clean_targets.len().min(24).min(cpu_workers.max(1))
+        }
+    };
+
+    let queue: Arc<Mutex<VecDeque<(usize, String)>>> = Arc::new(Mutex::new(
+        clean_targets
+            .iter()
+            .cloned()
+            .enumerate()
+            .collect::<VecDeque<(usize, String)>>(),
+    ));
+    let results: Arc<Mutex<Vec<(usize, FpingHostResult)>>> =
+        Arc::new(Mutex::new(Vec::with_capacity(clean_targets.len())));
+
+    let cancel_flag = Arc::new(AtomicBool::new(false));
+    let stop_after = stop_after.filter(|n| *n > 0);
+    let alive_count = Arc::new(AtomicU32::new(0));
+    let scan_key = scan_id
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty());
+    if let Some(ref key) = scan_key {
+        let mut registry = match FPING_SCANS.lock() {
+            Ok(g) => g,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        registry.insert(key.clone(), Arc::clone(&cancel_flag));
+    }
+
+    let mut workers = Vec::with_capacity(worker_count);
+    for _ in 0..worker_count {
+        let queue_ref = Arc::clone(&queue);
+        let results_ref = Arc::clone(&results);
+        let cancel_ref = Arc::clone(&cancel_flag);
+        let alive_ref = Arc::clone(&alive_count);
+        let timeout_clone = timeout.clone();
+        let source_clone = source_ip.clone();
+        workers.push(thread::spawn(move || loop {
+            if cancel_ref.load(Ordering::Relaxed) {
+                break;
+            }
+
+            let next_job = {
+                let mut guard = match queue_ref.lock() {
+                    Ok(g) => g,
+                    Err(poisoned) => poisoned.into_inner(),
+                };
+                guard.pop_front()
+            };
+
+            let (index, target) = match next_job {
+                Some(job) => job,
+                None => break,
+            };
+
+            let result =
+                ping_target_from(target, &timeout_clone, packet_count, source_clone.as_deref());
+            if let Some(limit) = stop_after {
+                // Reuse the cancel flag so idle workers stop pulling targets;
+                // pings already in flight still finish and are reported.
+                if result.success && alive_ref.fetch_add(1, Ordering::Relaxed) + 1 >= limit {
+                    cancel_ref.store(true, Ordering::Relaxed);
+                }
+            }
+            let mut out_guard = match results_ref.lock() {
+                Ok(g) => g,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+            out_guard.push((index, result));
+        }));
+    }
+
+    for worker in workers {
+        let _ = worker.join();
+    }
+
+    if let Some(ref key) = scan_key {
+        let mut registry = match FPING_SCANS.lock() {
+            Ok(g) => g,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        registry.remove(key);
+    }
+    let stopped_early = stop_after.is_some_and(|limit| alive_count.load(Ordering::Relaxed) >= limit);
+    let cancelled = cancel_flag.load(Ordering::Relaxed) && !stopped_early;
+
+    let mut ordered_results = {
+        let guard = match results.lock() {
+            Ok(g) => g,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        guard.clone()
+    };
+    ordered_results.sort_by_key(|(index, _)| *index);
+    let mut hosts: Vec<FpingHostResult> = ordered_results
+        .into_iter()
+        .map(|(_, host_result)| host_result)
+        .collect();
+
+    let sent = hosts.iter().map(|h| h.sent).sum::<u32>();
+    let received = hosts.iter().map(|h| h.received).sum::<u32>();
+    let loss_percent = if sent == 0 {
+        100.0
+    } else {
+        ((sent - received) as f32 / sent as f32) * 100.0
+    };
+
+    let mut min_ms = 0;
+    let mut max_ms = 0;
+    let mut avg_ms = 0;
+    let alive_latencies: Vec<u32> = hosts
+        .iter()
+        .filter(|h| h.success)
+        .map(|h| h.latency_ms)
+        .collect();
+
+    if !alive_latencies.is_empty() {
+        min_ms = *alive_latencies.iter().min().unwrap_or(&0);
+        max_ms = *alive_latencies.iter().max().unwrap_or(&0);
+        avg_ms = alive_latencies.iter().sum::<u32>() / alive_latencies.len() as u32;
+    }
+
+    match sort_key.as_deref() {
+        Some("latency") => hosts.sort_by_key(|h| (!h.success, h.latency_ms)),
+        // Numeric IP order (10.0.0.2 before 10.0.0.10), then hostnames by name
+        Some("target") => hosts.sort_by_cached_key(|h| {
+            let ip = h.target.parse::<std::net::IpAddr>().ok();
+            (ip.is_none(), ip, h.target.clone())
+        }),
+        Some("status") => hosts.sort_by_key(|h| !h.success),
+        _ => {}
+    }
+
+    Ok(FpingScanResult {
+        sent,
+        received,
+        loss_percent,
+        min_ms,
+        avg_ms,
+        max_ms,
+        hosts,
+        cancelled,
+        stopped_early,
+        workers: worker_count as u32,
+    })
+}
+
+/// Check internet connectivity; results are cached briefly unless `force` is set.
+/// Runs on the blocking pool so callers waiting on the cache lock never stall
+/// async-runtime workers.
+#[tauri::command(async)]
+pub fn check_internet(force: bool) -> Result<bool, NetworkError> {
+    // Holding the lock across the probe makes concurrent callers wait for
+    // the in-flight result instead of each opening their own connection.
+    let mut cache = match INTERNET_CHECK_CACHE.lock() {
+        Ok(g) => g,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    if !force {
+        if let Some((checked_at, online)) = *cache {
+            if checked_at.elapsed() < INTERNET_CHECK_TTL {
+                return Ok(online);
+            }
+        }
+    }
+    let online = probe_internet().is_some();
+    *cache = Some((Instant::now(), online));
+    Ok(online)
+}
+
+/// Create a simple allow/block firewall rule for a single TCP/UDP port
+#[tauri::command]
+pub async fn add_firewall_rule(
+    name: String,
+    direction: String,
+    action: String,
+    protocol: String,
+    port: u16,
+) -> Result<CommandResult, NetworkError> {
+    require_admin("Creating a firewall rule")?;
+
+    let rule_name = name.trim();
+    if rule_name.is_empty() {
+        return Err(NetworkError::invalid("Rule name is required"));
+    }
+    let direction = normalize_firewall_direction(&direction)
+        .ok_or_else(|| NetworkError::invalid("Direction must be inbound or outbound"))?;
+    let action = normalize_firewall_action(&action)
+        .ok_or_else(|| NetworkError::invalid("Action must be allow or block"))?;
+    let protocol = normalize_firewall_protocol(&protocol)
+        .ok_or_else(|| NetworkError::invalid("Protocol must be TCP or UDP"))?;
+    if port == 0 {
+        return Err(NetworkError::invalid("Port must be between 1 and 65535"));
+    }
+
+    // Inbound rules match the local listening port, outbound rules the remote one.
+    let port_param = if direction == "Inbound" {
+        "LocalPort"
+    } else {
+        "RemotePort"
+    };
+    let escaped_name = ps_escape_single_quoted(rule_name);
+    let script = format!(
+        r#"
+try {{
+  $rule = New-NetFirewallRule -DisplayName '{escaped_name}' -Direction {direction} -Action {action} -Protocol {protocol} -{port_param} {port} -ErrorAction Stop
+  Write-Output "[OK] Firewall rule created: $($rule.DisplayName)"
+  Write-Output "Name: $($rule.Name)"
+  Write-Output "Direction: $($rule.Direction)"
+  Write-Output "Action: $($rule.Action)"
+  Write-Output "Protocol: {protocol}"
+  Write-Output "{port_param}: {port}"
+  Write-Output "Enabled: $($rule.Enabled)"
+  Write-Output "Profile: $($rule.Profile)"
+}} catch {{
+  Write-Output "[FAIL] Firewall rule create error: $($_.Exception.Message)"
+}}
+"#
+    );
+
+    let output = run_powershell(&script)?;
+    let clean_output = output.trim().to_string();
+
+    Ok(CommandResult {
+        success: clean_output.contains("[OK]") && !clean_output.contains("[FAIL]"),
+        output: clean_output,
+    })
+}
+
+/// Get the current user's system (WinINET) proxy configuration
+#[tauri::command]
+pub async fn get_proxy_settings() -> Result<ProxySettings, NetworkError> {
+    read_proxy_settings()
+}
+
+/// Apply the system (WinINET) proxy configuration and return the effective settings
+#[tauri::command]
+pub async fn set_proxy_settings(settings: ProxySettings) -> Result<ProxySettings, NetworkError> {
+    let server = settings.server.trim();
+    if settings.enabled && server.is_empty() {
+        return Err(NetworkError::invalid(
+            "Proxy server is required when the proxy is enabled",
+        ));
+    }
+    if server.chars().any(|ch| ch.is_whitespace() || ch == ';') {
+        return Err(NetworkError::invalid(
+            "Proxy server must not contain spaces or ';'",
+        ));
+    }
+
+    let bypass_entries: Vec<&str> = settings
+        .bypass_list
+        .iter()
+        .map(|entry| entry.trim())
+        .filter(|entry| !entry.is_empty())
+        .collect();
+    if bypass_entries
+        .iter()
+        .any(|entry| entry.chars().any(|ch| ch.is_whitespace() || ch == ';'))
+    {
+        return Err(NetworkError::invalid(
+            "Bypass entries must not contain spaces or ';'",
+        ));
+    }
+
+    let escaped_server = ps_escape_single_quoted(server);
+    let escaped_bypass = ps_escape_single_quoted(&bypass_entries.join(";"));
+    let enable_value = if settings.enabled { 1 } else { 0 };
+    let script = format!(
+        r#"
+$ErrorActionPreference='Stop'
+$key = '{INTERNET_SETTINGS_KEY}'
+Set-ItemProperty -Path $key -Name ProxyEnable -Value {enable_value} -Type DWord
+Set-ItemProperty -Path $key -Name ProxyServer -Value '{escaped_server}' -Type String
+Set-ItemProperty -Path $key -Name ProxyOverride -Value '{escaped_bypass}' -Type String
+"#
+    );
+    run_powershell(&script)?;
+
+    read_proxy_settings()
+}
+
+/// Measure download throughput by streaming a test file over HTTP
+#[tauri::command]
+pub async fn run_speed_test(url: Option<String>) -> Result<SpeedTestResult, NetworkError> {
+    let test_url = url
+        .map(|u| u.trim().to_string())
+        .filter(|u| !u.is_empty())
+        .unwrap_or_else(|| DEFAULT_SPEED_TEST_URL.to_string());
+    let max_duration = Duration::from_secs(SPEED_TEST_MAX_SECS);
+
+    let start = Instant::now();
+    let mut response = http_get(&test_url, Duration::from_secs(5))?;
+    if !(200..300).contains(&response.status) {
+        let location = response
+            .headers
+            .get("location")
+            .map(|loc| format!(" (redirects to {})", loc))
+            .unwrap_or_default();
+        return Err(NetworkError::io(format!(
+            "Speed test server returned HTTP {}{}",
+            response.status, location
+        )));
+    }
+
+    // Chunk framing would be counted as payload, so only plain bodies are measured.
+    let chunked = response
+        .headers
+        .get("transfer-encoding")
+        .is_some_and(|value| value.to_lowercase().contains("chunked"));
+    if chunked {
+        return Err(NetworkError::io(
+            "Speed test server uses chunked transfer encoding, which is not supported",
+        ));
+    }
+    let content_length = response
+        .headers
+        .get("content-length")
+        .and_then(|value| value.parse::<u64>().ok());
+
+    // Keep individual reads short so a stalled link can't overrun the cap.
+    let _ = response.stream.set_read_timeout(Some(Duration::from_secs(2)));
+    let mut bytes = response.body_prefix.len() as u64;
+    let mut chunk = vec![0u8; 64 * 1024];
+    while start.elapsed() < max_duration {
+        if content_length.is_some_and(|len| bytes >= len) {
+            break;
+        }
+        match response.stream.read(&mut chunk) {
+            Ok(0) => {
+                if let Some(len) = content_length.filter(|len| bytes < *len) {
+                    return Err(NetworkError::io(format!(
+                        "Download truncated after {} of {} bytes",
+                        bytes, len
+                    )));
+                }
+                break;
+            }
+            Ok(read) => bytes += read as u64,
+            Err(e)
+                if e.kind() == std::io::ErrorKind::WouldBlock
+                    || e.kind() == std::io::ErrorKind::TimedOut =>
+            {
+                return Err(NetworkError::io(format!(
+                    "Download stalled after {} bytes",
+                    bytes
+                )));
+            }
+            Err(e) => return Err(NetworkError::io(format!("Download failed: {}", e))),
+        }
+    }
+
+    let seconds = start.elapsed().as_secs_f32().max(0.001);
+    let download_mbps = (bytes as f32 * 8.0) / seconds / 1_000_000.0;
+
+    Ok(SpeedTestResult {
+        download_mbps,
+        bytes,
+        seconds,
+    })
+}
+
+/// Save a named set of routes as a JSON profile in the app data directory
+#[tauri::command]
+pub async fn save_route_profile(
+    app: tauri::AppHandle,
+    name: String,
+    routes: Vec<RouteSpec>,
+) -> Result<CommandResult, NetworkError> {
+    let profile_name = sanitize_profile_name(&name)?;
+    if routes.is_empty() {
+        return Err(NetworkError::invalid(
+            "Profile must contain at least one route",
+        ));
+    }
+
+    let path = route_profiles_dir(&app)?.join(format!("{}.json", profile_name));
+    let json = serde_json::to_string_pretty(&routes)
+        .map_err(|e| NetworkError::parse(format!("Failed to serialize profile: {}", e)))?;
+    fs::write(&path, json).map_err(|e| {
+        NetworkError::io(format!(
+            "Failed to write profile file: {} ({})",
+            path.display(),
+            e
+        ))
+    })?;
+
+    Ok(CommandResult {
+        success: true,
+        output: format!(
+            "Saved profile '{}' with {} route(s).",
+            profile_name,
+            routes.len()
+        ),
+    })
+}
+
+/// List saved route profile names
+#[tauri::command]
+pub async fn list_route_profiles(app: tauri::AppHandle) -> Result<Vec<String>, NetworkError> {
+    let dir = route_profiles_dir(&app)?;
+    let entries = fs::read_dir(&dir).map_err(|e| {
+        NetworkError::io(format!(
+            "Failed to read profile directory: {} ({})",
+            dir.display(),
+            e
+        ))
+    })?;
+
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().map(|ext| ext == "json").unwrap_or(false))
+        .filter_map(|path| {
+            path.file_stem()
+                .map(|stem| stem.to_string_lossy().to_string())
+        })
+        .collect();
+    names.sort_by_key(|name| name.to_lowercase());
+    Ok(names)
+}
+
+/// Apply a saved route profile, optionally removing conflicting routes first
+#[tauri::command]
+pub async fn apply_route_profile(
+    app: tauri::AppHandle,
+    name: String,
+    flush_conflicting: Option<bool>,
+) -> Result<CommandResult, NetworkError> {
+    require_admin("Applying a route profile")?;
+
+    let profile_name = sanitize_profile_name(&name)?;
+    let path = route_profiles_dir(&app)?.join(format!("{}.json", profile_name));
+    let json = fs::read_to_string(&path).map_err(|e| {
+        NetworkError::io(format!("Failed to read profile '{}': {}", profile_name, e))
+    })?;
+    let routes: Vec<RouteSpec> = serde_json::from_str(&json).map_err(|e| {
+        NetworkError::parse(format!("Invalid profile file '{}': {}", profile_name, e))
+    })?;
+
+    let mut output_lines = vec![
+        format!("Applying profile '{}' ({} route(s)).", profile_name, routes.len()),
+        String::new(),
+    ];
+
+    if flush_conflicting.unwrap_or(false) {
+        let destinations: HashSet<&str> =
+            routes.iter().map(|route| route.destination.as_str()).collect();
+        for existing in read_routing_table(None)?
+            .iter()
+            .filter(|entry| destinations.contains(entry.destination.as_str()))
+        {
+            match run_cmd(
+                "route",
+                &["delete", &existing.destination, "mask", &existing.netmask],
+            ) {
+                Ok(_) => output_lines.push(format!(
+                    "[OK] Removed conflicting route {} mask {}",
+                    existing.destination, existing.netmask
+                )),
+                Err(err) => output_lines.push(format!(
+                    "[FAIL] Remove conflicting route {} mask {}: {}",
+                    existing.destination,
+                    existing.netmask,
+                    err.to_string().trim()
+                )),
+            }
+        }
+    }
+
+    let mut added = 0u32;
+    let mut failed = 0u32;
+    for route in &routes {
+        match add_route_entry(route) {
+            Ok(_) => {
+                added += 1;
+                output_lines.push(format!(
+                    "[OK] {} mask {} via {}",
+                    route.destination, route.mask, route.gateway
+                ));
+            }
+            Err(err) => {
+                failed += 1;
+                output_lines.push(format!(
+                    "[FAIL] {} mask {} via {}: {}",
+                    route.destination,
+                    route.mask,
+                    route.gateway,
+                    err.to_string().trim()
+                ));
+            }
+        }
+    }
+
+    output_lines.push(String::new());
+    output_lines.push(format!("Summary: added={} failed={}", added, failed));
+
+    let summary = output_lines.join("\n");
+    let audit_result = if failed == 0 {
+        Ok(summary.clone())
+    } else {
+        Err(NetworkError::command_failed(
+            "apply_route_profile",
+            summary.clone(),
+        ))
+    };
+    log_route_mutation(
+        &app,
+        "apply_route_profile",
+        serde_json::json!({ "name": profile_name, "flush_conflicting": flush_conflicting }),
+        &audit_result,
+    );
+
+    Ok(CommandResult {
+        success: failed == 0,
+        output: summary,
+    })
+}
+
+/// Get the preferred (lowest metric) IPv4 default gateway and its interface
+#[tauri::command]
+pub async fn get_default_gateway() -> Result<DefaultGateway, NetworkError> {
+    read_default_gateways()?
+        .into_iter()
+        .next()
+        .ok_or_else(|| NetworkError::not_found("No default route found"))
+}
+
+/// Every IPv4 default route, lowest (preferred) metric first
+#[tauri::command]
+pub async fn get_default_gateways() -> Result<Vec<DefaultGateway>, NetworkError> {
+    read_default_gateways()
+}
+
+/// Get the command prefixes accepted by run_network_command
+#[tauri::command]
+pub async fn get_allowed_commands() -> Result<Vec<String>, NetworkError> {
+    Ok(ALLOWED_COMMAND_PREFIXES
+        .iter()
+        .map(|prefix| (*prefix).to_string())
+        .collect())
+}
+
+/// Get per-adapter IP configuration parsed from `ipconfig /all`
+#[tauri::command]
+pub async fn get_ip_configuration() -> Result<Vec<AdapterConfig>, NetworkError> {
+    let output = run_cmd("ipconfig", &["/all"])?;
+    Ok(parse_ipconfig_all(&output))
+}
+
+/// Run a one-click network health check built from the individual probes
+#[tauri::command]
+pub async fn run_network_diagnostics() -> Result<NetworkDiagnostics, NetworkError> {
+    let mut steps: Vec<DiagnosticStep> = Vec::new();
+
+    let default_gateways = read_default_gateways().unwrap_or_default();
+    let default_route_count = default_gateways.len() as u32;
+    steps.push(DiagnosticStep {
+        name: "Default route".to_string(),
+        passed: default_route_count > 0,
+        latency_ms: None,
+        detail: format!("{} default route(s) found", default_route_count),
+    });
+
+    let gateway_ok = match default_gateways.first() {
+        Some(gw) if !gw.gateway.is_empty() && gw.gateway != "0.0.0.0" => {
+            let step = ping_step("Ping default gateway", &gw.gateway);
+            let passed = step.passed;
+            steps.push(step);
+            passed
+        }
+        _ => {
+            steps.push(DiagnosticStep {
+                name: "Ping default gateway".to_string(),
+                passed: false,
+                latency_ms: None,
+                detail: "No default gateway to ping".to_string(),
+            });
+            false
+        }
+    };
+
+    let internet_latency = probe_internet();
+    steps.push(DiagnosticStep {
+        name: format!("Internet (TCP {})", INTERNET_PROBE_ADDR),
+        passed: internet_latency.is_some(),
+        latency_ms: internet_latency,
+        detail: if internet_latency.is_some() {
+            "Connected".to_string()
+        } else {
+            "Connection failed".to_string()
+        },
+    });
+
+    let ping_public = ping_step("Ping 8.8.8.8", "8.8.8.8");
+    let ping_public_ok = ping_public.passed;
+    steps.push(ping_public);
+
+    let dns_start = Instant::now();
+    let dns_result = resolve_hostname(DNS_TEST_HOST);
+    let dns_ok = matches!(&dns_result, Ok(addrs) if !addrs.is_empty());
+    steps.push(DiagnosticStep {
+        name: format!("DNS lookup ({})", DNS_TEST_HOST),
+        passed: dns_ok,
+        latency_ms: if dns_ok {
+            Some(dns_start.elapsed().as_millis() as u32)
+        } else {
+            None
+        },
+        detail: match &dns_result {
+            Ok(addrs) if !addrs.is_empty() => format!("Resolved to {}", addrs[0]),
+            Ok(_) => "No addresses returned".to_string(),
+            Err(err) => err.to_string(),
+        },
+    });
+
+    let internet_ok = internet_latency.is_some() || ping_public_ok;
+    let verdict = if default_route_count == 0 {
+        "No default route: this machine has no path to the internet."
+    } else if !gateway_ok && !internet_ok {
+        "Default gateway is unreachable: check the local link (cable/Wi-Fi/router)."
+    } else if !internet_ok {
+        "Gateway reachable but no internet access: check the router or ISP."
+    } else if !dns_ok {
+        "Internet reachable but DNS resolution is failing: check DNS settings."
+    } else {
+        "Network looks healthy."
+    };
+
+    Ok(NetworkDiagnostics {
+        healthy: steps.iter().all(|step| step.passed),
+        steps,
+        default_route_count,
+        verdict: verdict.to_string(),
+    })
+}
+
+/// Cancel an in-flight fping scan; returns false when no scan has that id
+#[tauri::command]
+pub async fn cancel_fping_scan(scan_id: String) -> Result<bool, NetworkError> {
+    let registry = match FPING_SCANS.lock() {
+        Ok(g) => g,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    match registry.get(scan_id.trim()) {
+        Some(flag) => {
+            flag.store(true, Ordering::Relaxed);
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Read back the route mutation audit log (oldest first)
+#[tauri::command]
+pub async fn get_route_audit_log(app: tauri::AppHandle) -> Result<Vec<AuditEntry>, NetworkError> {
+    let path = app_data_dir(&app)?.join(ROUTE_AUDIT_LOG_FILE);
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+
+    let content = fs::read_to_string(&path).map_err(|e| {
+        NetworkError::io(format!(
+            "Failed to read audit log: {} ({})",
+            path.display(),
+            e
+        ))
+    })?;
+    Ok(content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str::<AuditEntry>(line).ok())
+        .collect())
+}
+
+/// Report whether the app runs in degraded (non-admin) mode and which features are usable
+#[tauri::command]
+pub async fn get_runtime_capabilities() -> Result<RuntimeCapabilities, NetworkError> {
+    let degraded = DEGRADED_MODE.load(Ordering::Relaxed);
+    let (available, unavailable): (Vec<_>, Vec<_>) = RUNTIME_FEATURES
+        .iter()
+        .partition(|(_, requires_admin)| !degraded || !requires_admin);
+
+    Ok(RuntimeCapabilities {
+        degraded,
+        available_features: available.iter().map(|(id, _)| (*id).to_string()).collect(),
+        unavailable_features: unavailable.iter().map(|(id, _)| (*id).to_string()).collect(),
+    })
+}
+
+/// Whether a mutation category ("route_change", "nic_config", "bloatware_removal",
+/// "cache_cleanup", or any other runtime feature id) would be allowed right now.
+/// Re-checks elevation live and that the tools the operation shells out to exist.
+#[tauri::command]
+pub async fn can_perform(operation: String) -> Result<bool, NetworkError> {
+    let operation = operation.trim().to_lowercase();
+    let requires_admin = RUNTIME_FEATURES
+        .iter()
+        .find(|(id, _)| *id == operation)
+        .map(|(_, requires_admin)| *requires_admin)
+        .ok_or_else(|| NetworkError::invalid(format!("Unknown operation: {}", operation)))?;
+
+    if requires_admin {
+        let elevated =
+            crate::is_running_as_admin().unwrap_or(!DEGRADED_MODE.load(Ordering::Relaxed));
+        if !elevated {
+            return Ok(false);
+        }
+    }
+
+    // The binaries the commands behind each category shell out to
+    let tools: &[&str] = match operation.as_str() {
+        "route_change" => &["route", "powershell"],
+        "route_profiles" => &["route"],
+        "nic_config" => &["netsh", "ipconfig", "powershell"],
+        "network_repair" => &["netsh", "ipconfig", "nbtstat", "arp"],
+        "firewall_rules" | "bloatware_removal" | "cache_cleanup" => &["powershell"],
+        _ => &[],
+    };
+    Ok(tools.iter().all(|tool| crate::command_exists(tool)))
+}
+
+/// Run the common network stack repair sequence (DNS, Winsock, TCP/IP, ARP)
+#[tauri::command]
+pub async fn repair_network_stack() -> Result<CommandResult, NetworkError> {
+    require_admin("Repairing the network stack")?;
+
+    let steps: [(&str, &[&str]); 4] = [
+        ("ipconfig", &["/flushdns"]),
+        ("netsh", &["winsock", "reset"]),
+        ("netsh", &["int", "ip", "reset"]),
+        ("netsh", &["interface", "ip", "delete", "arpcache"]),
+    ];
+
+    let mut output_lines = vec![
+        format!("Running network stack repair ({} steps).", steps.len()),
+        String::new(),
+    ];
+    let (ok_count, failed_count) = run_command_steps(&steps, &mut output_lines);
+
+    output_lines.push(format!(
+        "Summary: success={} failed={}",
+        ok_count, failed_count
+    ));
+    output_lines.push("A reboot is recommended to complete the Winsock/TCP-IP reset.".to_string());
+
+    Ok(CommandResult {
+        success: failed_count == 0,
+        output: output_lines.join("\n"),
+    })
+}
+
+/// Check IPv4, IPv6 and DNS connectivity (probes run in parallel)
+#[tauri::command]
+pub async fn check_connectivity() -> Result<ConnectivityStatus, NetworkError> {
+    let ipv4_probe = thread::spawn(|| probe_internet().is_some());
+    let ipv6_probe =
+        thread::spawn(|| tcp_probe(INTERNET_PROBE_ADDR_V6, Duration::from_secs(3)).is_some());
+    let dns_probe = thread::spawn(|| {
+        resolve_hostname(DNS_TEST_HOST)
+            .map(|addrs| !addrs.is_empty())
+            .unwrap_or(false)
+    });
+
+    Ok(ConnectivityStatus {
+        ipv4: ipv4_probe.join().unwrap_or(false),
+        ipv6: ipv6_probe.join().unwrap_or(false),
+        dns: dns_probe.join().unwrap_or(false),
+    })
+}
+
+/// Start pinging the default gateway on a timer, emitting `gateway-status` events
+#[tauri::command]
+pub async fn start_gateway_monitor(
+    window: tauri::Window,
+    interval_ms: u32,
+) -> Result<(), NetworkError> {
+    let interval = Duration::from_millis(interval_ms.clamp(500, 60_000) as u64);
+    let stop_flag = Arc::new(AtomicBool::new(false));
+
+    {
+        let mut guard = match GATEWAY_MONITOR.lock() {
+            Ok(g) => g,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        if let Some(previous) = guard.replace(Arc::clone(&stop_flag)) {
+            previous.store(true, Ordering::Relaxed);
+        }
+    }
+
+    thread::spawn(move || {
+        while !stop_flag.load(Ordering::Relaxed) {
+            // Re-detect every tick so the monitor follows network switches
+            let gateway = read_default_gateways()
+                .ok()
+                .and_then(|gateways| gateways.into_iter().next())
+                .map(|gw| gw.gateway)
+                .filter(|gw| !gw.is_empty() && gw != "0.0.0.0");
+
+            let status = match gateway {
+                Some(gw) => {
+                    let result = ping_once_target(gw.clone(), "1000", 1);
+                    GatewayStatus {
+                        gateway: gw,
+                        reachable: result.success,
+                        latency_ms: result.latency_ms,
+                    }
+                }
+                None => GatewayStatus {
+                    gateway: String::new(),
+                    reachable: false,
+                    latency_ms: 0,
+                },
+            };
+
+            if stop_flag.load(Ordering::Relaxed) {
+                break;
+            }
+            if window.emit("gateway-status", &status).is_err() {
+                break;
+            }
+            sleep_unless_stopped(interval, &stop_flag);
+        }
+    });
+
+    Ok(())
+}
+
+/// Stop the running gateway monitor; returns false when none was running
+#[tauri::command]
+pub async fn stop_gateway_monitor() -> Result<bool, NetworkError> {
+    let mut guard = match GATEWAY_MONITOR.lock() {
+        Ok(g) => g,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    match guard.take() {
+        Some(flag) => {
+            flag.store(true, Ordering::Relaxed);
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Ping a curated set of well-known services in parallel
+#[tauri::command]
+pub async fn measure_service_latency() -> Result<Vec<ServiceLatency>, NetworkError> {
+    let jobs: Vec<(&str, &str)> = SERVICE_ENDPOINTS.to_vec();
+    let results = run_ordered_pool(jobs, SERVICE_ENDPOINTS.len(), |(name, endpoint)| {
+        let result = ping_once_target(endpoint.to_string(), "2000", 1);
+        ServiceLatency {
+            name: name.to_string(),
+            endpoint: endpoint.to_string(),
+            latency_ms: if result.success { result.latency_ms } else { 0 },
+            reachable: result.success,
+        }
+    });
+    Ok(results)
+}
+
+/// Run Test-NetConnection against host[:port] and return a structured result
+#[tauri::command]
+pub async fn test_connection(
+    host: String,
+    port: Option<u16>,
+) -> Result<TcpTestResult, NetworkError> {
+    let host = host.trim();
+    if !is_safe_host_token(host) {
+        return Err(NetworkError::invalid("Invalid host"));
+    }
+
+    let port_arg = match port {
+        Some(p) if p > 0 => format!(" -Port {}", p),
+        Some(_) => return Err(NetworkError::invalid("Port must be between 1 and 65535")),
+        None => String::new(),
+    };
+    let script = format!(
+        r#"
+        Test-NetConnection -ComputerName '{host}'{port_arg} -WarningAction SilentlyContinue |
+        Select-Object ComputerName,
+            @{{n='RemoteAddress';e={{"$($_.RemoteAddress)"}}}},
+            TcpTestSucceeded,
+            PingSucceeded,
+            @{{n='PingLatency';e={{$_.PingReplyDetails.RoundtripTime}}}} |
+        ConvertTo-Json -Compress
+    "#
+    );
+
+    let output = run_powershell(&script)?;
+    let data: serde_json::Value = serde_json::from_str(output.trim())
+        .map_err(|e| NetworkError::parse(format!("JSON parse error: {}", e)))?;
+
+    Ok(TcpTestResult {
+        computer: data["ComputerName"].as_str().unwrap_or(host).to_string(),
+        remote_address: data["RemoteAddress"].as_str().unwrap_or("").to_string(),
+        port_open: data["TcpTestSucceeded"].as_bool().unwrap_or(false),
+        ping_succeeded: data["PingSucceeded"].as_bool().unwrap_or(false),
+        ping_latency_ms: data["PingLatency"].as_u64().unwrap_or(0) as u32,
+    })
+}
+
+/// Release and renew the DHCP lease for one adapter (or all when no index is given)
+#[tauri::command]
+pub async fn renew_dhcp_lease(
+    interface_index: Option<String>,
+) -> Result<DhcpRenewResult, NetworkError> {
+    require_admin("Renewing the DHCP lease")?;
+
+    let index = match interface_index.as_deref().map(str::trim) {
+        Some(idx) if !idx.is_empty() => Some(validate_interface_index(idx)?),
+        _ => None,
+    };
+    let alias = match index {
+        Some(ref idx) => Some(interface_alias(idx)?),
+        None => None,
+    };
+
+    let before_ips = interface_ipv4_addresses(index.as_deref());
+
+    let mut release_args = vec!["/release"];
+    let mut renew_args = vec!["/renew"];
+    if let Some(ref name) = alias {
+        release_args.push(name);
+        renew_args.push(name);
+    }
+
+    let mut output_lines = vec![
+        format!(
+            "Renewing DHCP lease for {}.",
+            alias.as_deref().unwrap_or("all adapters")
+        ),
+        String::new(),
+    ];
+    let steps: [(&str, &[&str]); 2] = [("ipconfig", &release_args), ("ipconfig", &renew_args)];
+    let (_, failed_count) = run_command_steps(&steps, &mut output_lines);
+
+    let after_ips = interface_ipv4_addresses(index.as_deref());
+    output_lines.push(format!("Before: {}", before_ips.join(", ")));
+    output_lines.push(format!("After: {}", after_ips.join(", ")));
+
+    Ok(DhcpRenewResult {
+        success: failed_count == 0,
+        interface_alias: alias,
+        before_ips,
+        after_ips,
+        output: output_lines.join("\n"),
+    })
+}
+
+/// Re-register a removed bloatware candidate from its on-disk AppX manifest
+#[tauri::command]
+pub async fn reinstall_appx_package(package_name: String) -> Result<CommandResult, NetworkError> {
+    require_admin("Reinstalling AppX packages")?;
+
+    let lower = package_name.trim().to_lowercase();
+    let canonical = bloatware_catalog()
+        .into_iter()
+        .find(|(name, _, _, _)| name.to_lowercase() == lower)
+        .map(|(name, _, _, _)| name)
+        .ok_or_else(|| {
+            NetworkError::invalid(format!(
+                "Package is not a known bloatware candidate: {}",
+                package_name
+            ))
+        })?;
+
+    let escaped_name = ps_escape_single_quoted(canonical);
+    let script = format!(
+        r#"
+$pkgName = '{escaped_name}'
+$registered = 0
+$hasFailure = $false
+$candidates = Get-AppxPackage -AllUsers -Name $pkgName -ErrorAction SilentlyContinue
+foreach ($pkg in $candidates) {{
+  $manifest = Join-Path $pkg.InstallLocation 'AppXManifest.xml'
+  if (-not ($pkg.InstallLocation -and (Test-Path $manifest))) {{ continue }}
+  try {{
+    Add-AppxPackage -DisableDevelopmentMode -Register $manifest -ErrorAction Stop | Out-Null
+    $registered++
+  }} catch {{
+    $hasFailure = $true
+    Write-Output "[FAIL] $pkgName register error: $($_.Exception.Message)"
+  }}
+}}
+if ($registered -gt 0) {{
+  Write-Output "[OK] $pkgName re-registered from $registered manifest(s)"
+}} elseif (-not $hasFailure) {{
+  Write-Output "[FAIL] $pkgName package files are gone; reinstall it from the Microsoft Store"
+}}
+"#
+    );
+
+    let output = run_powershell(&script)?;
+    let clean_output = output.trim().to_string();
+    Ok(CommandResult {
+        success: clean_output.contains("[OK]") && !clean_output.contains("[FAIL]"),
+        output: clean_output,
+    })
+}
+
+/// List scheduled tasks, optionally limited to one task folder (e.g. `\Microsoft\Windows\Maps\`)
+#[tauri::command]
+pub async fn get_scheduled_tasks(
+    folder: Option<String>,
+) -> Result<Vec<ScheduledTask>, NetworkError> {
+    let path_filter = match folder.as_deref().map(str::trim).filter(|f| !f.is_empty()) {
+        Some(folder) => {
+            let safe = folder.starts_with('\\')
+                && folder.chars().all(|ch| {
+                    ch.is_ascii_alphanumeric() || matches!(ch, '\\' | ' ' | '.' | '_' | '-')
+                });
+            if !safe {
+                return Err(NetworkError::invalid(format!(
+                    "Invalid task folder: {}",
+                    folder
+                )));
+            }
+            let folder = if folder.ends_with('\\') {
+                folder.to_string()
+            } else {
+                format!("{}\\", folder)
+            };
+            format!("-TaskPath '{}'", ps_escape_single_quoted(&folder))
+        }
+        None => String::new(),
+    };
+
+    let script = format!(
+        r#"
+        Get-ScheduledTask {path_filter} -ErrorAction SilentlyContinue |
+        ForEach-Object {{
+            [PSCustomObject]@{{
+                Name = $_.TaskName
+                Path = $_.TaskPath
+                State = $_.State.ToString()
+                Description = $_.Description
+            }}
+        }} |
+        ConvertTo-Json -Compress
+    "#
+    );
+
+    let output = run_powershell(&script)?;
+    let tasks = parse_json_rows(&output)?
+        .iter()
+        .map(|item| ScheduledTask {
+            name: item["Name"].as_str().unwrap_or("").to_string(),
+            path: item["Path"].as_str().unwrap_or("").to_string(),
+            state: item["State"].as_str().unwrap_or("").to_string(),
+            description: item["Description"].as_str().unwrap_or("").to_string(),
+        })
+        .collect();
+    Ok(tasks)
+}
+
+/// Enable or disable a scheduled task from the known-safe allowlist
+#[tauri::command]
+pub async fn set_scheduled_task_state(
+    path: String,
+    name: String,
+    enabled: bool,
+) -> Result<CommandResult, NetworkError> {
+    require_admin("Changing scheduled tasks")?;
+
+    let (task_path, task_name) = SAFE_SCHEDULED_TASKS
+        .iter()
+        .find(|(task_path, task_name)| {
+            task_path.eq_ignore_ascii_case(path.trim()) && task_name.eq_ignore_ascii_case(name.trim())
+        })
+        .ok_or_else(|| {
+            NetworkError::invalid(format!(
+                "Task is not in the safe-to-toggle list: {}{}",
+                path, name
+            ))
+        })?;
+
+    let cmdlet = if enabled {
+        "Enable-ScheduledTask"
+    } else {
+        "Disable-ScheduledTask"
+    };
+    let script = format!(
+        "{} -TaskPath '{}' -TaskName '{}' -ErrorAction Stop | Select-Object -ExpandProperty State",
+        cmdlet,
+        ps_escape_single_quoted(task_path),
+        ps_escape_single_quoted(task_name)
+    );
+
+    match run_powershell(&script) {
+        Ok(state) => Ok(CommandResult {
+            success: true,
+            output: format!("[OK] {}{} is now {}", task_path, task_name, state.trim()),
+        }),
+        Err(e) => Ok(CommandResult {
+            success: false,
+            output: format!("[FAIL] {}{}: {}", task_path, task_name, e),
+        }),
+    }
+}
+
+/// List Windows services, optionally filtered by a substring of the name or display name
+#[tauri::command]
+pub async fn get_services(
+    name_filter: Option<String>,
+) -> Result<Vec<WindowsService>, NetworkError> {
+    let filter = name_filter.unwrap_or_default().trim().to_string();
+    if !filter
+        .chars()
+        .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, ' ' | '.' | '_' | '-'))
+    {
+        return Err(NetworkError::invalid(format!(
+            "Invalid service filter: {}",
+            filter
+        )));
+    }
+
+    let script = format!(
+        r#"
+        $filter = '*{}*'
+        Get-Service -ErrorAction SilentlyContinue |
+        Where-Object {{ $_.Name -like $filter -or $_.DisplayName -like $filter }} |
+        ForEach-Object {{
+            [PSCustomObject]@{{
+                Name = $_.Name
+                DisplayName = $_.DisplayName
+                Status = $_.Status.ToString()
+                StartType = $_.StartType.ToString()
+            }}
+        }} |
+        ConvertTo-Json -Compress
+    "#,
+        ps_escape_single_quoted(&filter)
+    );
+
+    let output = run_powershell(&script)?;
+    let services = parse_json_rows(&output)?
+        .iter()
+        .map(|item| WindowsService {
+            name: item["Name"].as_str().unwrap_or("").to_string(),
+            display_name: item["DisplayName"].as_str().unwrap_or("").to_string(),
+            status: item["Status"].as_str().unwrap_or("").to_string(),
+            start_type: item["StartType"].as_str().unwrap_or("").to_string(),
+        })
+        .collect();
+    Ok(services)
+}
+
+/// Start, stop, or restart an allowlisted non-critical service
+#[tauri::command]
+pub async fn set_service_state(
+    name: String,
+    action: String,
+) -> Result<CommandResult, NetworkError> {
+    require_admin("Controlling services")?;
+
+    let service = CONTROLLABLE_SERVICES
+        .iter()
+        .find(|service| service.eq_ignore_ascii_case(name.trim()))
+        .ok_or_else(|| {
+            NetworkError::invalid(format!("Service is not in the controllable list: {}", name))
+        })?;
+    let cmdlet = match action.trim().to_lowercase().as_str() {
+        "start" => "Start-Service",
+        "stop" => "Stop-Service -Force",
+        "restart" => "Restart-Service -Force",
+        _ => {
+            return Err(NetworkError::invalid(format!(
+                "Invalid service action: {}",
+                action
+            )))
+        }
+    };
+
+    let script = format!(
+        "{} -Name '{}' -ErrorAction Stop; (Get-Service -Name '{}').Status.ToString()",
+        cmdlet,
+        ps_escape_single_quoted(service),
+        ps_escape_single_quoted(service)
+    );
+
+    match run_powershell(&script) {
+        Ok(status) => Ok(CommandResult {
+            success: true,
+            output: format!("[OK] {} is now {}", service, status.trim()),
+        }),
+        Err(e) => Ok(CommandResult {
+            success: false,
+            output: format!("[FAIL] {} {}: {}", action.trim(), service, e),
+        }),
+    }
+}
+
+/// List startup programs from the Run keys and Startup folders
+#[tauri::command]
+pub async fn get_startup_items() -> Result<Vec<StartupItem>, NetworkError> {
+    let script = format!(
+        r#"
+$approvedRoot = '{STARTUP_APPROVED_KEY}'
+function Get-StartupApproved($hive, $sub, $name) {{
+  $value = (Get-ItemProperty -Path "$($hive):\$approvedRoot\$sub" -Name $name -ErrorAction SilentlyContinue).$name
+  if ($value -and $value.Length -gt 0) {{ return ($value[0] % 2) -eq 0 }}
+  return $true
+}}
+$skip = 'PSPath','PSParentPath','PSChildName','PSDrive','PSProvider'
+$items = @()
+foreach ($hive in 'HKCU','HKLM') {{
+  $run = Get-ItemProperty -Path "$($hive):\Software\Microsoft\Windows\CurrentVersion\Run" -ErrorAction SilentlyContinue
+  if (-not $run) {{ continue }}
+  foreach ($prop in $run.PSObject.Properties) {{
+    if ($skip -contains $prop.Name) {{ continue }}
+    $items += [PSCustomObject]@{{
+      Name = $prop.Name
+      Command = [string]$prop.Value
+      Location = "$hive\Run"
+      Enabled = Get-StartupApproved $hive 'Run' $prop.Name
+    }}
+  }}
+}}
+foreach ($folder in @(@('HKCU','Startup'), @('HKLM','CommonStartup'))) {{
+  $dir = [Environment]::GetFolderPath($folder[1])
+  Get-ChildItem -Path $dir -File -ErrorAction SilentlyContinue |
+    Where-Object {{ $_.Name -ne 'desktop.ini' }} |
+    ForEach-Object {{
+      $items += [PSCustomObject]@{{
+        Name = $_.Name
+        Command = $_.FullName
+        Location = "$($folder[0])\StartupFolder"
+        Enabled = Get-StartupApproved $folder[0] 'StartupFolder' $_.Name
+      }}
+    }}
+}}
+$items | ConvertTo-Json -Compress
+"#
+    );
+
+    let output = run_powershell(&script)?;
+    let items = parse_json_rows(&output)?
+        .iter()
+        .map(|item| StartupItem {
+            name: item["Name"].as_str().unwrap_or("").to_string(),
+            command: item["Command"].as_str().unwrap_or("").to_string(),
+            location: item["Location"].as_str().unwrap_or("").to_string(),
+            enabled: item["Enabled"].as_bool().unwrap_or(true),
+        })
+        .collect();
+    Ok(items)
+}
+
+/// Enable or disable a startup item the same way Task Manager does (StartupApproved flags)
+#[tauri::command]
+pub async fn set_startup_item_enabled(
+    location: String,
+    name: String,
+    enabled: bool,
+) -> Result<CommandResult, NetworkError> {
+    require_admin("Changing startup items")?;
+
+    let (location_id, hive, approved_sub) = STARTUP_LOCATIONS
+        .iter()
+        .find(|(id, _, _)| id.eq_ignore_ascii_case(location.trim()))
+        .ok_or_else(|| NetworkError::invalid(format!("Invalid startup location: {}", location)))?;
+    let name = name.trim();
+    if name.is_empty() || name.chars().any(|ch| ch.is_control()) {
+        return Err(NetworkError::invalid("Invalid startup item name"));
+    }
+    let escaped_name = ps_escape_single_quoted(name);
+
+    let exists_check = if *approved_sub == "Run" {
+        format!(
+            "$null -ne (Get-ItemProperty -Path '{}:\\Software\\Microsoft\\Windows\\CurrentVersion\\Run' -Name '{}' -ErrorAction SilentlyContinue)",
+            hive, escaped_name
+        )
+    } else {
+        let folder = if *hive == "HKCU" { "Startup" } else { "CommonStartup" };
+        format!(
+            "Test-Path -LiteralPath (Join-Path ([Environment]::GetFolderPath('{}')) '{}')",
+            folder, escaped_name
+        )
+    };
+    // Task Manager writes 0x02 (enabled) or 0x03 (disabled) followed by a timestamp.
+    let flag = if enabled { "0x02" } else { "0x03" };
+    let script = format!(
+        r#"
+if (-not ({exists_check})) {{
+  Write-Output "[FAIL] Startup item not found"
+  exit 1
+}}
+$path = '{hive}:\{STARTUP_APPROVED_KEY}\{approved_sub}'
+if (-not (Test-Path $path)) {{ New-Item -Path $path -Force | Out-Null }}
+$bytes = [byte[]]({flag},0,0,0,0,0,0,0,0,0,0,0)
+New-ItemProperty -Path $path -Name '{escaped_name}' -PropertyType Binary -Value $bytes -Force | Out-Null
+Write-Output "[OK]"
+"#
+    );
+
+    match run_powershell(&script) {
+        Ok(_) => Ok(CommandResult {
+            success: true,
+            output: format!(
+                "[OK] {} ({}) {}",
+                name,
+                location_id,
+                if enabled { "enabled" } else { "disabled" }
+            ),
+        }),
+        Err(e) => Ok(CommandResult {
+            success: false,
+            output: format!(
+                "[FAIL] {} ({}): {}",
+                name,
+                location_id,
+                e.to_string().trim()
+            ),
+        }),
+    }
+}
+
+/// Resolve PTR hostnames for a list of IPs (e.g. live hosts from an fping sweep)
+#[tauri::command]
+pub async fn reverse_dns(ips: Vec<String>) -> Result<Vec<ReverseDnsResult>, NetworkError> {
+    let clean_ips: Vec<String> = ips
+        .into_iter()
+        .map(|ip| ip.trim().to_string())
+        .filter(|ip| ip.parse::<std::net::IpAddr>().is_ok())
+        .take(256)
+        .collect();
+
+    if clean_ips.is_empty() {
+        return Err(NetworkError::invalid("No valid IP addresses provided"));
+    }
+
+    let cpu_workers = thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(4);
+    let worker_count = clean_ips.len().min(16).min(cpu_workers.max(1));
+
+    let results = run_ordered_pool(clean_ips, worker_count, |ip| ReverseDnsResult {
+        hostname: reverse_lookup(&ip),
+        ip,
+    });
+    Ok(results)
+}
+
+/// Ping-sweep the local subnet to fill the ARP cache, then list the neighbors that answered
+#[tauri::command]
+pub async fn discover_lan_devices(
+    interface_index: Option<String>,
+) -> Result<Vec<LanDevice>, NetworkError> {
+    let interface_index = match interface_index {
+        Some(idx) => validate_interface_index(&idx)?,
+        None => read_default_gateways()?
+            .into_iter()
+            .next()
+            .map(|gw| gw.interface_index)
+            .ok_or_else(|| {
+                NetworkError::not_found("No default gateway found to pick an interface")
+            })?,
+    };
+
+    let address_script = format!(
+        "Get-NetIPAddress -AddressFamily IPv4 -InterfaceIndex {} -ErrorAction Stop | Select-Object -First 1 IPAddress, PrefixLength | ConvertTo-Json -Compress",
+        interface_index
+    );
+    let address = parse_json_rows(&run_powershell(&address_script)?)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| {
+            NetworkError::not_found(format!("Interface {} has no IPv4 address", interface_index))
+        })?;
+    let local_ip: std::net::Ipv4Addr = address["IPAddress"]
+        .as_str()
+        .unwrap_or("")
+        .parse()
+        .map_err(|_| {
+            NetworkError::not_found(format!("Interface {} has no IPv4 address", interface_index))
+        })?;
+    // Larger subnets are swept only around our own /24 to keep the scan bounded.
+    let prefix = address["PrefixLength"].as_u64().unwrap_or(24).clamp(24, 30) as u32;
+    let mask = u32::MAX << (32 - prefix);
+    let network = u32::from(local_ip) & mask;
+    let broadcast = network | !mask;
+
+    let sweep: Vec<String> = (network + 1..broadcast)
+        .map(std::net::Ipv4Addr::from)
+        .filter(|ip| *ip != local_ip)
+        .map(|ip| ip.to_string())
+        .collect();
+    run_ordered_pool(sweep, 32, |ip| ping_once_target(ip, "300", 1));
+
+    let neighbor_script = format!(
+        r#"
+        Get-NetNeighbor -AddressFamily IPv4 -InterfaceIndex {} -ErrorAction SilentlyContinue |
+        Where-Object {{
+            $_.State -ne 'Unreachable' -and $_.LinkLayerAddress -and
+            $_.LinkLayerAddress -ne '00-00-00-00-00-00' -and $_.LinkLayerAddress -ne 'FF-FF-FF-FF-FF-FF'
+        }} |
+        Select-Object IPAddress, LinkLayerAddress |
+        ConvertTo-Json -Compress
+    "#,
+        interface_index
+    );
+    let mut neighbors: Vec<(std::net::Ipv4Addr, String)> =
+        parse_json_rows(&run_powershell(&neighbor_script)?)?
+            .iter()
+            .filter_map(|item| {
+                let ip: std::net::Ipv4Addr = item["IPAddress"].as_str()?.parse().ok()?;
+                let in_subnet = u32::from(ip) & mask == network && u32::from(ip) != broadcast;
+                in_subnet.then(|| (ip, item["LinkLayerAddress"].as_str().unwrap_or("").to_string()))
+            })
+            .collect();
+    neighbors.sort_by_key(|(ip, _)| u32::from(*ip));
+
+    let devices = run_ordered_pool(neighbors, 16, |(ip, mac)| {
+        let ip = ip.to_string();
+        LanDevice {
+            hostname: reverse_lookup(&ip),
+            vendor: lookup_oui_vendor(&mac).map(str::to_string),
+            ip,
+            mac,
+        }
+    });
+    Ok(devices)
+}
+
+/// Vendor name for a MAC address, or `None` when the OUI prefix is unknown
+#[tauri::command]
+pub async fn lookup_mac_vendor(mac: String) -> Option<String> {
+    lookup_oui_vendor(&mac).map(str::to_string)
+}
+
+/// Read the DNS resolver cache as a table
+#[tauri::command]
+pub async fn get_dns_cache() -> Result<Vec<DnsCacheEntry>, NetworkError> {
+    let output = run_cmd("ipconfig", &["/displaydns"])?;
+    Ok(parse_displaydns(&output))
+}
+
+/// Flush the DNS resolver cache
+#[tauri::command]
+pub async fn flush_dns_cache() -> Result<CommandResult, NetworkError> {
+    let steps: [(&str, &[&str]); 1] = [("ipconfig", &["/flushdns"])];
+    let mut output_lines = Vec::new();
+    let (_, failed_count) = run_command_steps(&steps, &mut output_lines);
+
+    Ok(CommandResult {
+        success: failed_count == 0,
+        output: output_lines.join("\n"),
+    })
+}
+
+/// Ping the default gateway and a public host once per second for `duration_secs`,
+/// emitting `connection-quality-sample` events. A sample counts as up when the public host replies.
+/// Runs on the blocking pool; `stop_connection_quality_monitor` ends it early.
+#[tauri::command(async)]
+pub fn monitor_connection_quality(
+    duration_secs: u32,
+    window: tauri::Window,
+) -> Result<ConnectionQualityReport, NetworkError> {
+    let duration_secs = duration_secs.clamp(1, 3600);
+    let gateway = read_default_gateways()
+        .ok()
+        .and_then(|gateways| gateways.into_iter().next())
+        .map(|gw| gw.gateway)
+        .filter(|gw| !gw.is_empty() && gw != "0.0.0.0");
+
+    let mut samples = 0u32;
+    let mut successful = 0u32;
+    let mut latency_total = 0u64;
+    let mut current_gap = 0u32;
+    let mut max_gap_secs = 0u32;
+    let stop_flag = start_monitor_run(&QUALITY_MONITOR);
+
+    for second in 0..duration_secs {
+        if stop_flag.load(Ordering::Relaxed) {
+            break;
+        }
+        let tick_start = Instant::now();
+        let mut targets = vec![PUBLIC_PING_HOST.to_string()];
+        targets.extend(gateway.clone());
+        let mut results = run_ordered_pool(targets, 2, |target| {
+            ping_once_target(target, "900", 1)
+        })
+        .into_iter();
+
+        let internet = results.next();
+        let gateway_result = results.next();
+        let internet_reachable = internet.as_ref().map(|r| r.success).unwrap_or(false);
+        let internet_latency_ms = internet.as_ref().map(|r| r.latency_ms).unwrap_or(0);
+
+        samples += 1;
+        if internet_reachable {
+            successful += 1;
+            latency_total += internet_latency_ms as u64;
+            current_gap = 0;
+        } else {
+            current_gap += 1;
+            max_gap_secs = max_gap_secs.max(current_gap);
+        }
+
+        let sample = ConnectionQualitySample {
+            second,
+            gateway: GatewayStatus {
+                gateway: gateway.clone().unwrap_or_default(),
+                reachable: gateway_result.as_ref().map(|r| r.success).unwrap_or(false),
+                latency_ms: gateway_result.as_ref().map(|r| r.latency_ms).unwrap_or(0),
+            },
+            internet_reachable,
+            internet_latency_ms,
+        };
+        if window.emit("connection-quality-sample", &sample).is_err() {
+            break;
+        }
+
+        if let Some(rest) = Duration::from_secs(1).checked_sub(tick_start.elapsed()) {
+            sleep_unless_stopped(rest, &stop_flag);
+        }
+    }
+    finish_monitor_run(&QUALITY_MONITOR, &stop_flag);
+
+    Ok(ConnectionQualityReport {
+        samples,
+        successful,
+        uptime_percent: if samples == 0 {
+            0.0
+        } else {
+            (successful as f32 / samples as f32) * 100.0
+        },
+        avg_latency_ms: if successful == 0 {
+            0
+        } else {
+            (latency_total / successful as u64) as u32
+        },
+        max_gap_secs,
+    })
+}
+
+/// Stop the running `monitor_connection_quality`; returns false when none was running
+#[tauri::command]
+pub async fn stop_connection_quality_monitor() -> Result<bool, NetworkError> {
+    Ok(stop_monitor_run(&QUALITY_MONITOR))
+}
+
+/// Change the metric of an existing route without deleting and re-adding it
+#[tauri::command]
+pub async fn set_route_metric(
+    app: tauri::AppHandle,
+    destination: String,
+    mask: String,
+    metric: u32,
+) -> Result<RouteEntry, NetworkError> {
+    require_admin("Changing a route metric")?;
+
+    let destination = destination
+        .trim()
+        .parse::<std::net::Ipv4Addr>()
+        .map_err(|_| NetworkError::invalid(format!("Invalid destination: {}", destination)))?
+        .to_string();
+    let prefix = mask_to_prefix(&mask)?;
+    if !(1..=9999).contains(&metric) {
+        return Err(NetworkError::invalid(format!(
+            "Metric must be between 1 and 9999, got {}",
+            metric
+        )));
+    }
+
+    let cidr = format!("{}/{}", destination, prefix);
+    let script = format!(
+        "Set-NetRoute -DestinationPrefix '{}' -RouteMetric {} -ErrorAction Stop",
+        cidr, metric
+    );
+    let result = run_powershell(&script);
+    log_route_mutation(
+        &app,
+        "set_route_metric",
+        serde_json::json!({ "destination": destination, "mask": mask, "metric": metric }),
+        &result,
+    );
+    result?;
+
+    let netmask = prefix_to_mask(prefix);
+    read_routing_table(None)?
+        .into_iter()
+        .find(|route| route.destination == destination && route.netmask == netmask)
+        .ok_or_else(|| NetworkError::not_found(format!("Route {} not found after update", cidr)))
+}
+
+/// Ask Windows which route, source IP and interface it would use to reach `destination`
+#[tauri::command]
+pub async fn find_route_for_destination(destination: String) -> Result<RouteLookup, NetworkError> {
+    let destination = destination
+        .trim()
+        .parse::<std::net::Ipv4Addr>()
+        .map_err(|_| NetworkError::invalid(format!("Invalid IPv4 destination: {}", destination)))?;
+    find_route(destination)
+}
+
+/// Export the interface list to `path` as "csv" or "json"
+#[tauri::command]
+pub async fn export_interfaces(
+    path: String,
+    format: String,
+) -> Result<CommandResult, NetworkError> {
+    let interfaces = get_network_interfaces(false, None).await?;
+
+    let content = match format.trim().to_lowercase().as_str() {
+        "json" => serde_json::to_string_pretty(&interfaces)
+            .map_err(|e| NetworkError::parse(format!("Failed to serialize interfaces: {}", e)))?,
+        "csv" => {
+            let mut lines = vec!["index,ip,gateway,description".to_string()];
+            lines.extend(interfaces.iter().map(|iface| {
+                [&iface.index, &iface.ip, &iface.gateway, &iface.description]
+                    .iter()
+                    .map(|field| csv_field(field))
+                    .collect::<Vec<String>>()
+                    .join(",")
+            }));
+            lines.join("\r\n") + "\r\n"
+        }
+        other => {
+            return Err(NetworkError::invalid(format!(
+                "Unsupported export format: {}",
+                other
+            )))
+        }
+    };
+
+    fs::write(&path, content)
+        .map_err(|e| NetworkError::io(format!("Failed to write {}: {}", path, e)))?;
+    Ok(CommandResult {
+        success: true,
+        output: format!("Exported {} interface(s) to {}", interfaces.len(), path),
+    })
+}
+
+/// Export the bloatware scan (installed and absent candidates) to `path` as "csv" or "json"
+#[tauri::command]
+pub async fn export_bloatware_report(
+    path: String,
+    format: String,
+) -> Result<CommandResult, NetworkError> {
+    let items = get_bloatware_candidates().await?;
+
+    let content = match format.trim().to_lowercase().as_str() {
+        "json" => serde_json::to_string_pretty(&items).map_err(|e| {
+            NetworkError::parse(format!("Failed to serialize bloatware scan: {}", e))
+        })?,
+        "csv" => {
+            let mut lines = vec!["package_name,label,category,vendor,installed".to_string()];
+            lines.extend(items.iter().map(|item| {
+                [
+                    item.package_name.as_str(),
+                    item.label.as_str(),
+                    item.category.as_str(),
+                    item.vendor.as_deref().unwrap_or(""),
+                    if item.installed { "true" } else { "false" },
+                ]
+                .iter()
+                .map(|field| csv_field(field))
+                .collect::<Vec<String>>()
+                .join(",")
+            }));
+            lines.join("\r\n") + "\r\n"
+        }
+        other => {
+            return Err(NetworkError::invalid(format!(
+                "Unsupported export format: {}",
+                other
+            )))
+        }
+    };
+
+    fs::write(&path, content)
+        .map_err(|e| NetworkError::io(format!("Failed to write {}: {}", path, e)))?;
+    let installed = items.iter().filter(|item| item.installed).count();
+    Ok(CommandResult {
+        success: true,
+        output: format!(
+            "Exported {} bloatware candidate(s) ({} installed) to {}",
+            items.len(),
+            installed,
+            path
+        ),
+    })
+}
+
+/// Write interfaces, routes, gateway, DNS config, connectivity and the environment report
+/// to one JSON file for attaching to bug reports
+#[tauri::command]
+pub async fn generate_diagnostics_bundle(
+    app: tauri::AppHandle,
+    path: String,
+) -> Result<CommandResult, NetworkError> {
+    let bundle = serde_json::json!({
+        "generated_at": unix_timestamp(),
+        "app_version": app.package_info().version.to_string(),
+        "environment": report_section(Ok(crate::collect_environment_report())),
+        "interfaces": report_section(get_network_interfaces(false, None).await),
+        "routing_table": report_section(read_routing_table(None)),
+        "default_gateway": report_section(get_default_gateway().await),
+        "ip_configuration": report_section(get_ip_configuration().await),
+        "connectivity": report_section(check_connectivity().await),
+    });
+
+    let content = serde_json::to_string_pretty(&bundle)
+        .map_err(|e| NetworkError::parse(format!("Failed to serialize diagnostics: {}", e)))?;
+    fs::write(&path, content)
+        .map_err(|e| NetworkError::io(format!("Failed to write {}: {}", path, e)))?;
+    Ok(CommandResult {
+        success: true,
+        output: format!("Diagnostics bundle written to {}", path),
+    })
+}
+
+/// The interface carrying the active (lowest-metric) default route, or `None` when offline
+#[tauri::command]
+pub async fn get_primary_interface() -> Result<Option<NetworkInterface>, NetworkError> {
+    let primary_index = match read_default_gateways()?.into_iter().next() {
+        Some(gw) => gw.interface_index,
+        None => return Ok(None),
+    };
+    Ok(get_network_interfaces(false, None)
+        .await?
+        .into_iter()
+        .find(|iface| iface.index == primary_index))
+}
+
+/// Delete every route to `destination`, whatever its mask
+#[tauri::command]
+pub async fn delete_routes_to(
+    app: tauri::AppHandle,
+    destination: String,
+) -> Result<CommandResult, NetworkError> {
+    require_admin("Deleting routes")?;
+
+    let destination = destination
+        .trim()
+        .parse::<std::net::Ipv4Addr>()
+        .map_err(|_| NetworkError::invalid(format!("Invalid destination: {}", destination)))?
+        .to_string();
+
+    let mut masks: Vec<String> = Vec::new();
+    for route in read_routing_table(None)? {
+        if route.destination == destination && !masks.contains(&route.netmask) {
+            masks.push(route.netmask);
+        }
+    }
+    if masks.is_empty() {
+        return Ok(CommandResult {
+            success: true,
+            output: format!("[SKIP] No routes to {}", destination),
+        });
+    }
+
+    let mut output_lines = Vec::new();
+    let mut deleted = 0u32;
+    for mask in &masks {
+        let result = run_cmd("route", &["delete", &destination, "mask", mask]);
+        log_route_mutation(
+            &app,
+            "delete_route",
+            serde_json::json!({ "destination": destination, "mask": mask }),
+            &result,
+        );
+        match result {
+            Ok(_) => {
+                deleted += 1;
+                output_lines.push(format!("[OK] {} mask {}", destination, mask));
+            }
+            Err(e) => output_lines.push(format!(
+                "[FAIL] {} mask {}: {}",
+                destination,
+                mask,
+                e.to_string().trim()
+            )),
+        }
+    }
+    output_lines.push(format!("Deleted {}/{} route(s)", deleted, masks.len()));
+
+    Ok(CommandResult {
+        success: deleted as usize == masks.len(),
+        output: output_lines.join("\n"),
+    })
+}
+
+/// Delete manually added, non-persistent routes while keeping the default route,
+/// persistent routes and the system's own connected/link routes
+#[tauri::command]
+pub async fn flush_temporary_routes(app: tauri::AppHandle) -> Result<CommandResult, NetworkError> {
+    require_admin("Flushing temporary routes")?;
+
+    let ps_script = r#"
+        $persistent = @(
+            Get-NetRoute -AddressFamily IPv4 -PolicyStore PersistentStore -ErrorAction SilentlyContinue |
+            ForEach-Object { "$($_.DestinationPrefix)|$($_.NextHop)" }
+        )
+        Get-NetRoute -AddressFamily IPv4 -PolicyStore ActiveStore -ErrorAction SilentlyContinue |
+        Where-Object {
+            $_.Protocol -eq 'NetMgmt' -and
+            $_.DestinationPrefix -ne '0.0.0.0/0' -and
+            $persistent -notcontains "$($_.DestinationPrefix)|$($_.NextHop)"
+        } |
+        Select-Object DestinationPrefix, NextHop, RouteMetric, InterfaceIndex |
+        ConvertTo-Json -Compress
+    "#;
+
+    let candidates = parse_json_rows(&run_powershell(ps_script)?)?;
+    if candidates.is_empty() {
+        return Ok(CommandResult {
+            success: true,
+            output: "[SKIP] No temporary routes to remove".to_string(),
+        });
+    }
+
+    let mut output_lines = Vec::new();
+    let mut failed = 0u32;
+    for item in &candidates {
+        let route = route_entry_from_json(item);
+        let label = format!(
+            "{} mask {} via {} (if {})",
+            route.destination, route.netmask, route.gateway, route.interface_index
+        );
+        let interface_index = match validate_interface_index(&route.interface_index) {
+            Ok(idx) => idx,
+            Err(e) => {
+                failed += 1;
+                output_lines.push(format!("[FAIL] {}: {}", label, e.to_string().trim()));
+                continue;
+            }
         };
+        let script = format!(
+            "Remove-NetRoute -DestinationPrefix '{}' -NextHop '{}' -InterfaceIndex {} -PolicyStore ActiveStore -Confirm:$false -ErrorAction Stop",
+            ps_escape_single_quoted(item["DestinationPrefix"].as_str().unwrap_or("")),
+            ps_escape_single_quoted(&route.gateway),
+            interface_index
+        );
+        let result = run_powershell(&script);
+        log_route_mutation(&app, "flush_temporary_routes", serde_json::json!(route), &result);
+        match result {
+            Ok(_) => output_lines.push(format!("[OK] Removed {}", label)),
+            Err(e) => {
+                failed += 1;
+                output_lines.push(format!("[FAIL] {}: {}", label, e.to_string().trim()));
+            }
+        }
+    }
+    output_lines.push(format!(
+        "Summary: removed={} failed={}",
+        candidates.len() as u32 - failed,
+        failed
+    ));
+
+    Ok(CommandResult {
+        success: failed == 0,
+        output: output_lines.join("\n"),
+    })
+}
+
+/// Binary-search the largest unfragmented ping payload to `target` and derive the path MTU
+/// (payload + 28 bytes of IP/ICMP headers); optionally set it on the interface
+#[tauri::command]
+pub async fn find_optimal_mtu(
+    target: String,
+    interface_index: String,
+    apply: bool,
+) -> Result<MtuProbeResult, NetworkError> {
+    let target = target.trim().to_string();
+    if !is_safe_host_token(&target) {
+        return Err(NetworkError::invalid(format!("Invalid target: {}", target)));
+    }
+    let interface_index = validate_interface_index(&interface_index)?;
+    if apply {
+        require_admin("Changing the interface MTU")?;
+    }
+
+    // 548 + 28 = 576, the minimum MTU every IPv4 path must carry.
+    let (mut low, mut high) = (548u32, 1500 - ICMP_OVERHEAD);
+    if !ping_dont_fragment(&target, low) {
+        return Err(NetworkError::command_failed(
+            "ping",
+            format!(
+                "{} did not answer a {}-byte don't-fragment ping",
+                target, low
+            ),
+        ));
+    }
+    while low < high {
+        let mid = (low + high).div_ceil(2);
+        if ping_dont_fragment(&target, mid) {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+    let mtu = low + ICMP_OVERHEAD;
+    let mut output = format!(
+        "Largest unfragmented payload to {} is {} bytes (MTU {})",
+        target, low, mtu
+    );
+
+    let mut applied = false;
+    if apply {
+        let script = format!(
+            "Set-NetIPInterface -InterfaceIndex {} -AddressFamily IPv4 -NlMtuBytes {} -ErrorAction Stop",
+            interface_index, mtu
+        );
+        match run_powershell(&script) {
+            Ok(_) => {
+                applied = true;
+                output.push_str(&format!("\n[OK] MTU {} set on interface {}", mtu, interface_index));
+            }
+            Err(e) => output.push_str(&format!(
+                "\n[FAIL] Could not set MTU on interface {}: {}",
+                interface_index,
+                e.to_string().trim()
+            )),
+        }
+    }
+
+    Ok(MtuProbeResult {
+        target,
+        mtu,
+        largest_payload: low,
+        applied,
+        output,
+    })
+}
+
+/// IPv6 neighbor (NDP) table
+#[tauri::command]
+pub async fn get_ipv6_neighbors() -> Result<Vec<NeighborEntry>, NetworkError> {
+    read_neighbors("IPv6")
+}
+
+/// Enabled state of the Domain, Private and Public firewall profiles
+#[tauri::command]
+pub async fn get_firewall_profiles() -> Result<Vec<FirewallProfile>, NetworkError> {
+    read_firewall_profiles()
+}
 
-        let index = match &item["InterfaceIndex"] {
-            serde_json::Value::Number(n) => n.to_string(),
-            serde_json::Value::String(s) => s.clone(),
-            _ => String::new(),
-        };
+/// Enable or disable one firewall profile and return the refreshed states
+#[tauri::command]
+pub async fn set_firewall_profile(
+    profile: String,
+    enabled: bool,
+) -> Result<Vec<FirewallProfile>, NetworkError> {
+    require_admin("Changing the firewall profile")?;
 
-        interfaces.push(NetworkInterface {
-            index,
-            ip,
-            gateway,
-            description: desc,
-        });
+    let profile = match profile.trim().to_lowercase().as_str() {
+        "domain" => "Domain",
+        "private" => "Private",
+        "public" => "Public",
+        _ => {
+            return Err(NetworkError::invalid(format!(
+                "Invalid firewall profile: {}",
+                profile
+            )))
+        }
+    };
+    let script = format!(
+        "Set-NetFirewallProfile -Profile {} -Enabled {} -ErrorAction Stop",
+        profile,
+        if enabled { "True" } else { "False" }
+    );
+    run_powershell(&script)?;
+    read_firewall_profiles()
+}
+
+/// Time one `Resolve-DnsName` lookup of `test_domain` against each DNS server in parallel
+#[tauri::command]
+pub async fn benchmark_dns(
+    servers: Vec<String>,
+    test_domain: String,
+) -> Result<Vec<DnsBenchmark>, NetworkError> {
+    let test_domain = test_domain.trim().to_string();
+    if !is_safe_host_token(&test_domain) {
+        return Err(NetworkError::invalid(format!(
+            "Invalid test domain: {}",
+            test_domain
+        )));
+    }
+    let clean_servers: Vec<String> = servers
+        .into_iter()
+        .map(|s| s.trim().to_string())
+        .filter(|s| s.parse::<std::net::IpAddr>().is_ok())
+        .take(32)
+        .collect();
+    if clean_servers.is_empty() {
+        return Err(NetworkError::invalid(
+            "No valid DNS server addresses provided",
+        ));
     }
 
-    Ok(interfaces)
+    let worker_count = clean_servers.len().min(8);
+    let results = run_ordered_pool(clean_servers, worker_count, |server| {
+        let lookup = timed_dns_lookup(&test_domain, Some(&server));
+        DnsBenchmark {
+            latency_ms: *lookup.as_ref().unwrap_or(&0),
+            success: lookup.is_ok(),
+            server,
+        }
+    });
+    Ok(results)
+}
+
+/// List the power plans from `powercfg /list`
+#[tauri::command]
+pub async fn get_power_plans() -> Result<Vec<PowerPlan>, NetworkError> {
+    let output = run_cmd("powercfg", &["/list"])?;
+    Ok(parse_power_plans(&output))
+}
+
+/// Switch the active power plan
+#[tauri::command]
+pub async fn set_active_power_plan(guid: String) -> Result<Vec<PowerPlan>, NetworkError> {
+    require_admin("Changing the power plan")?;
+
+    let guid = guid.trim().trim_matches(|ch| ch == '{' || ch == '}').to_lowercase();
+    if !is_guid(&guid) {
+        return Err(NetworkError::invalid(format!(
+            "Invalid power plan GUID: {}",
+            guid
+        )));
+    }
+    run_cmd("powercfg", &["/setactive", &guid])?;
+    let plans = parse_power_plans(&run_cmd("powercfg", &["/list"])?);
+    if !plans.iter().any(|plan| plan.guid == guid && plan.active) {
+        return Err(NetworkError::command_failed(
+            "powercfg",
+            format!("Power plan {} was not activated", guid),
+        ));
+    }
+    Ok(plans)
 }
 
-/// Get IPv4 routing table
+/// Live battery charge, AC state and estimated runtime
 #[tauri::command]
-pub async fn get_routing_table() -> Result<Vec<RouteEntry>, String> {
+pub async fn get_battery_status() -> Result<BatteryStatus, NetworkError> {
+    // Win32_Battery.BatteryStatus codes: 1 discharging, 4/5 low/critical, 6-9 charging.
+    // root\wmi BatteryStatus is more precise for AC/charging when the driver exposes it.
     let ps_script = r#"
-        Get-NetRoute -AddressFamily IPv4 |
-        Select-Object DestinationPrefix, NextHop, RouteMetric, InterfaceIndex |
+        $battery = Get-CimInstance Win32_Battery -ErrorAction SilentlyContinue | Select-Object -First 1
+        if (-not $battery) { exit 0 }
+        $wmi = Get-CimInstance -Namespace root\wmi -ClassName BatteryStatus -ErrorAction SilentlyContinue | Select-Object -First 1
+        $code = [int]$battery.BatteryStatus
+        [PSCustomObject]@{
+            Charge = [int]$battery.EstimatedChargeRemaining
+            RunTime = [long]$battery.EstimatedRunTime
+            OnAc = if ($wmi) { [bool]$wmi.PowerOnline } else { @(1, 4, 5) -notcontains $code }
+            Charging = if ($wmi) { [bool]$wmi.Charging } else { $code -ge 6 -and $code -le 9 }
+        } | ConvertTo-Json -Compress
+    "#;
+
+    let item = parse_json_rows(&run_powershell(ps_script)?)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| NetworkError::not_found("No battery detected"))?;
+    let on_ac_power = item["OnAc"].as_bool().unwrap_or(false);
+    // 71582788 is WMI's "unknown" sentinel (reported while on AC power)
+    let run_time = item["RunTime"].as_u64().unwrap_or(0);
+    let time_remaining_mins = if on_ac_power || run_time == 0 || run_time >= 71_582_788 {
+        None
+    } else {
+        Some(run_time as u32)
+    };
+
+    Ok(BatteryStatus {
+        charge_percent: item["Charge"].as_u64().unwrap_or(0).min(100) as u8,
+        on_ac_power,
+        time_remaining_mins,
+        charging: item["Charging"].as_bool().unwrap_or(false),
+    })
+}
+
+/// Ping `count` times and return one sample per echo request (replies and drops)
+#[tauri::command]
+pub async fn ping_detailed(target: String, count: u32) -> Result<Vec<PingSample>, NetworkError> {
+    let target = target.trim().to_string();
+    if !is_safe_host_token(&target) {
+        return Err(NetworkError::invalid(format!("Invalid target: {}", target)));
+    }
+    let count = count.clamp(1, 100).to_string();
+
+    let output = Command::new("ping")
+        .args(["-n", &count, "-w", "2000", &target])
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+        .map_err(|e| NetworkError::command_failed("ping", format!("Ping failed: {}", e)))?;
+    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+
+    // Per-packet lines are the block right after the "Pinging ..." header,
+    // ending at the blank line before the statistics.
+    let samples = stdout
+        .lines()
+        .map(str::trim)
+        .skip_while(|line| line.is_empty())
+        .skip(1)
+        .take_while(|line| !line.is_empty())
+        .enumerate()
+        .map(|(index, line)| {
+            // IPv6 replies carry no TTL, so a reply is recognised by its time field
+            let success = line.contains("time=") || line.contains("time<");
+            let (latency_ms, ttl) = parse_ping_latency(line, 0);
+            PingSample {
+                seq: index as u32 + 1,
+                success,
+                latency_ms: if success { latency_ms } else { 0 },
+                ttl,
+            }
+        })
+        .collect();
+    Ok(samples)
+}
+
+/// Clear the NetBIOS, ARP and DNS caches in one go
+#[tauri::command]
+pub async fn clear_network_caches() -> Result<CommandResult, NetworkError> {
+    require_admin("Clearing network caches")?;
+
+    let steps: [(&str, &[&str]); 4] = [
+        ("nbtstat", &["-R"]),
+        ("nbtstat", &["-RR"]),
+        ("arp", &["-d", "*"]),
+        ("ipconfig", &["/flushdns"]),
+    ];
+
+    let mut output_lines = vec![
+        format!("Clearing network caches ({} steps).", steps.len()),
+        "nbtstat -R/-RR and arp -d require Administrator privileges.".to_string(),
+        String::new(),
+    ];
+    let (ok_count, failed_count) = run_command_steps(&steps, &mut output_lines);
+
+    output_lines.push(format!(
+        "Summary: success={} failed={}",
+        ok_count, failed_count
+    ));
+
+    Ok(CommandResult {
+        success: failed_count == 0,
+        output: output_lines.join("\n"),
+    })
+}
+
+/// Look for other devices answering for one of this machine's IPv4 addresses
+#[tauri::command]
+pub async fn detect_ip_conflicts() -> Result<Vec<IpConflict>, NetworkError> {
+    let ps_script = r#"
+        Get-NetIPAddress -AddressFamily IPv4 -ErrorAction SilentlyContinue |
+        Where-Object { $_.IPAddress -ne '127.0.0.1' -and $_.IPAddress -notlike '169.254.*' } |
+        ForEach-Object {
+            $adapter = Get-NetAdapter -InterfaceIndex $_.InterfaceIndex -ErrorAction SilentlyContinue
+            [PSCustomObject]@{
+                IPAddress = $_.IPAddress
+                InterfaceIndex = $_.InterfaceIndex
+                AddressState = $_.AddressState.ToString()
+                MacAddress = if ($adapter) { $adapter.MacAddress } else { '' }
+            }
+        } |
         ConvertTo-Json -Compress
     "#;
+    let local_addresses = parse_json_rows(&run_powershell(ps_script)?)?;
 
-    let output = run_powershell(ps_script)?;
-    let data: serde_json::Value =
-        serde_json::from_str(&output).map_err(|e| format!("JSON parse error: {}", e))?;
+    // Pinging our own addresses refreshes any ARP entry another host holds for them
+    let own_ips: Vec<String> = local_addresses
+        .iter()
+        .filter_map(|item| item["IPAddress"].as_str().map(str::to_string))
+        .collect();
+    run_ordered_pool(own_ips, 8, |ip| ping_once_target(ip, "500", 1));
+    let neighbors = read_neighbors("IPv4")?;
 
-    let items = match &data {
-        serde_json::Value::Array(arr) => arr.clone(),
-        obj @ serde_json::Value::Object(_) => vec![obj.clone()],
-        _ => return Ok(vec![]),
+    let mut conflicts = Vec::new();
+    for item in &local_addresses {
+        let ip = item["IPAddress"].as_str().unwrap_or("");
+        let local_mac = normalize_mac(item["MacAddress"].as_str().unwrap_or(""))
+            .map(|hex| dashed_mac(&hex))
+            .unwrap_or_default();
+        let conflicting_mac = neighbors
+            .iter()
+            .find(|n| n.ip == ip && n.mac != local_mac)
+            .map(|n| n.mac.clone());
+        let flagged_duplicate = item["AddressState"].as_str() == Some("Duplicate");
+
+        if conflicting_mac.is_some() || flagged_duplicate {
+            conflicts.push(IpConflict {
+                ip: ip.to_string(),
+                local_mac,
+                conflicting_mac,
+            });
+        }
+    }
+    Ok(conflicts)
+}
+
+/// Save IP/gateway/DNS settings of connected interfaces plus persistent routes to a JSON file
+#[tauri::command]
+pub async fn backup_network_config(path: String) -> Result<CommandResult, NetworkError> {
+    let ps_script = r#"
+        Get-NetIPInterface -AddressFamily IPv4 -ConnectionState Connected -ErrorAction SilentlyContinue |
+        Where-Object { $_.InterfaceAlias -notlike 'Loopback*' } |
+        ForEach-Object {
+            $idx = $_.InterfaceIndex
+            $addresses = @(
+                Get-NetIPAddress -AddressFamily IPv4 -InterfaceIndex $idx -ErrorAction SilentlyContinue |
+                Where-Object { $_.PrefixOrigin -ne 'WellKnown' } |
+                ForEach-Object { [PSCustomObject]@{ ip = $_.IPAddress; prefix_length = [int]$_.PrefixLength } }
+            )
+            $gateway = Get-NetRoute -AddressFamily IPv4 -InterfaceIndex $idx -DestinationPrefix 0.0.0.0/0 -ErrorAction SilentlyContinue |
+                Select-Object -First 1 -ExpandProperty NextHop
+            $dns = @(
+                (Get-DnsClientServerAddress -InterfaceIndex $idx -AddressFamily IPv4 -ErrorAction SilentlyContinue).ServerAddresses |
+                Where-Object { $_ }
+            )
+            $adapter = Get-NetAdapter -InterfaceIndex $idx -ErrorAction SilentlyContinue
+            $staticDns = $false
+            if ($adapter) {
+                $key = "HKLM:\SYSTEM\CurrentControlSet\Services\Tcpip\Parameters\Interfaces\$($adapter.InterfaceGuid)"
+                $staticDns = [bool](Get-ItemProperty -Path $key -Name NameServer -ErrorAction SilentlyContinue).NameServer
+            }
+            [PSCustomObject]@{
+                interface_index = [string]$idx
+                interface_alias = $_.InterfaceAlias
+                dhcp = ($_.Dhcp.ToString() -eq 'Enabled')
+                addresses = $addresses
+                gateway = $gateway
+                dns_static = $staticDns
+                dns_servers = $dns
+            }
+        } |
+        ConvertTo-Json -Compress -Depth 4
+    "#;
+    let interfaces: Vec<InterfaceConfigBackup> = parse_json_rows(&run_powershell(ps_script)?)?
+        .into_iter()
+        .map(|item| {
+            serde_json::from_value(item)
+                .map_err(|e| NetworkError::parse(format!("Unexpected interface data: {}", e)))
+        })
+        .collect::<Result<_, _>>()?;
+
+    let routes_script = r#"
+        Get-NetRoute -AddressFamily IPv4 -PolicyStore PersistentStore -ErrorAction SilentlyContinue |
+        Select-Object DestinationPrefix, NextHop, RouteMetric, InterfaceIndex |
+        ConvertTo-Json -Compress
+    "#;
+    let persistent_routes: Vec<RouteSpec> = parse_json_rows(&run_powershell(routes_script)?)?
+        .iter()
+        .map(route_entry_from_json)
+        .map(|route| RouteSpec {
+            destination: route.destination,
+            mask: route.netmask,
+            gateway: route.gateway,
+            metric: route.metric,
+            interface_index: Some(route.interface_index),
+        })
+        .collect();
+
+    let backup = NetworkConfigBackup {
+        created_at: unix_timestamp(),
+        interfaces,
+        persistent_routes,
     };
+    let json = serde_json::to_string_pretty(&backup)
+        .map_err(|e| NetworkError::parse(format!("Failed to serialize backup: {}", e)))?;
+    fs::write(&path, json)
+        .map_err(|e| NetworkError::io(format!("Failed to write {}: {}", path, e)))?;
 
-    let mut routes: Vec<RouteEntry> = Vec::new();
+    Ok(CommandResult {
+        success: true,
+        output: format!(
+            "Backed up {} interface(s) and {} persistent route(s) to {}",
+            backup.interfaces.len(),
+            backup.persistent_routes.len(),
+            path
+        ),
+    })
+}
 
-    for item in &items {
-        let prefix = item["DestinationPrefix"]
-            .as_str()
-            .unwrap_or("")
-            .to_string();
-        let next_hop = item["NextHop"].as_str().unwrap_or("").to_string();
+/// Re-apply a backup written by `backup_network_config`. Every value is validated
+/// before anything is changed.
+#[tauri::command]
+pub async fn restore_network_config(
+    app: tauri::AppHandle,
+    path: String,
+) -> Result<CommandResult, NetworkError> {
+    require_admin("Restoring the network configuration")?;
 
-        let metric = match &item["RouteMetric"] {
-            serde_json::Value::Number(n) => n.to_string(),
-            serde_json::Value::String(s) => s.clone(),
-            _ => "0".to_string(),
-        };
+    let json = fs::read_to_string(&path)
+        .map_err(|e| NetworkError::io(format!("Failed to read {}: {}", path, e)))?;
+    let backup: NetworkConfigBackup = serde_json::from_str(&json)
+        .map_err(|e| NetworkError::parse(format!("Invalid backup file: {}", e)))?;
 
-        let if_index = match &item["InterfaceIndex"] {
-            serde_json::Value::Number(n) => n.to_string(),
-            serde_json::Value::String(s) => s.clone(),
-            _ => "0".to_string(),
-        };
+    let mut problems: Vec<String> = Vec::new();
+    for iface in &backup.interfaces {
+        if let Err(e) = validate_interface_backup(iface) {
+            problems.push(format!("{} ({}): {}", iface.interface_alias, iface.interface_index, e));
+        }
+    }
+    for route in &backup.persistent_routes {
+        if let Err(e) = validate_route_spec(route) {
+            problems.push(format!("route {} mask {}: {}", route.destination, route.mask, e));
+        }
+    }
+    if !problems.is_empty() {
+        return Err(NetworkError::invalid(format!(
+            "Backup rejected, nothing was changed:\n{}",
+            problems.join("\n")
+        )));
+    }
 
-        // Split prefix into destination and mask
-        let (dest, mask) = if let Some(pos) = prefix.find('/') {
-            let ip = prefix[..pos].to_string();
-            let prefix_len: u32 = prefix[pos + 1..].parse().unwrap_or(32);
-            (ip, prefix_to_mask(prefix_len))
-        } else {
-            (prefix, "255.255.255.255".to_string())
-        };
+    let mut output_lines = Vec::new();
+    let mut failed = 0u32;
+    for iface in &backup.interfaces {
+        let label = format!("{} ({})", iface.interface_alias, iface.interface_index);
+        match run_powershell(&interface_restore_script(iface)) {
+            Ok(_) => output_lines.push(format!("[OK] Interface {}", label)),
+            Err(e) => {
+                failed += 1;
+                output_lines.push(format!(
+                    "[FAIL] Interface {}: {}",
+                    label,
+                    e.to_string().trim()
+                ));
+            }
+        }
+    }
+    for route in &backup.persistent_routes {
+        let result = add_route_entry(route);
+        log_route_mutation(&app, "restore_network_config", serde_json::json!(route), &result);
+        let label = format!("{} mask {} via {}", route.destination, route.mask, route.gateway);
+        match result {
+            Ok(_) => output_lines.push(format!("[OK] Route {}", label)),
+            Err(e) => {
+                failed += 1;
+                output_lines.push(format!("[FAIL] Route {}: {}", label, e.to_string().trim()));
+            }
+        }
+    }
+    output_lines.push(format!(
+        "Summary: applied={} failed={}",
+        (backup.interfaces.len() + backup.persistent_routes.len()) as u32 - failed,
+        failed
+    ));
 
-        routes.push(RouteEntry {
-            destination: dest,
-            netmask: mask,
-            gateway: next_hop,
-            metric,
-            interface_index: if_index,
-        });
+    Ok(CommandResult {
+        success: failed == 0,
+        output: output_lines.join("\n"),
+    })
+}
+
+/// Resolve `target`, show the route/interface Windows picks for it, then traceroute it
+#[tauri::command]
+pub async fn trace_path_to(target: String) -> Result<PathReport, NetworkError> {
+    let target = target.trim().to_string();
+    if !is_safe_host_token(&target) {
+        return Err(NetworkError::invalid(format!("Invalid target: {}", target)));
     }
+    let resolved = resolve_hostname(&target)?
+        .into_iter()
+        .find_map(|ip| match ip {
+            std::net::IpAddr::V4(v4) => Some(v4),
+            std::net::IpAddr::V6(_) => None,
+        })
+        .ok_or_else(|| NetworkError::not_found(format!("{} has no IPv4 address", target)))?;
+
+    let lookup = find_route(resolved)?;
+    let hops = run_traceroute(&resolved.to_string(), 30)?;
 
-    Ok(routes)
+    Ok(PathReport {
+        target,
+        resolved_ip: resolved.to_string(),
+        chosen_route: lookup.route,
+        source_ip: lookup.source_ip,
+        interface_alias: lookup.interface_alias,
+        hops,
+    })
 }
 
-/// Add a persistent route
+/// Every IPv4/IPv6 address on one interface, including secondary and link-local ones
 #[tauri::command]
-pub async fn add_route(
-    destination: String,
-    mask: String,
-    gateway: String,
-    metric: String,
-    interface_index: Option<String>,
-) -> Result<CommandResult, String> {
-    // First try to delete existing route
-    let _ = run_cmd("route", &["delete", &destination, "mask", &mask]);
+pub async fn get_interface_addresses(
+    interface_index: String,
+) -> Result<Vec<InterfaceAddress>, NetworkError> {
+    let idx = validate_interface_index(&interface_index)?;
+    let script = format!(
+        r#"
+        Get-NetIPAddress -InterfaceIndex {idx} -ErrorAction SilentlyContinue |
+        ForEach-Object {{
+            [PSCustomObject]@{{
+                ip = $_.IPAddress
+                prefix_length = [int]$_.PrefixLength
+                address_family = $_.AddressFamily.ToString()
+                origin = $_.PrefixOrigin.ToString()
+            }}
+        }} |
+        ConvertTo-Json -Compress
+    "#
+    );
 
-    let mut args = vec![
-        "route",
-        "-p",
-        "add",
-        &destination,
-        "mask",
-        &mask,
-        &gateway,
-        "metric",
-        &metric,
+    let addresses = parse_json_rows(&run_powershell(&script)?)?
+        .iter()
+        .map(|item| InterfaceAddress {
+            ip: item["ip"].as_str().unwrap_or("").to_string(),
+            prefix_length: item["prefix_length"].as_u64().unwrap_or(0) as u32,
+            address_family: item["address_family"].as_str().unwrap_or("").to_string(),
+            origin: item["origin"].as_str().unwrap_or("").to_string(),
+        })
+        .collect();
+    Ok(addresses)
+}
+
+/// Single TCP connect check against `host:port` (timeout default 2000 ms, clamped to 100..=10000)
+#[tauri::command]
+pub async fn is_port_open(
+    host: String,
+    port: u16,
+    timeout_ms: Option<u32>,
+) -> Result<PortCheck, NetworkError> {
+    let host = host.trim();
+    if !is_safe_host_token(host) {
+        return Err(NetworkError::invalid(format!("Invalid host: {}", host)));
+    }
+    if port == 0 {
+        return Err(NetworkError::invalid("Port must be between 1 and 65535"));
+    }
+    let timeout = Duration::from_millis(timeout_ms.unwrap_or(2000).clamp(100, 10_000) as u64);
+
+    let addrs = resolve_hostname(host)
+        .map_err(|_| NetworkError::not_found(format!("Could not resolve host {}", host)))?;
+    let ip = addrs
+        .iter()
+        .find(|ip| ip.is_ipv4())
+        .or_else(|| addrs.first())
+        .copied()
+        .ok_or_else(|| NetworkError::not_found(format!("Could not resolve host {}", host)))?;
+
+    let start = Instant::now();
+    let open = TcpStream::connect_timeout(&std::net::SocketAddr::new(ip, port), timeout).is_ok();
+    Ok(PortCheck {
+        open,
+        latency_ms: if open {
+            start.elapsed().as_millis() as u32
+        } else {
+            0
+        },
+    })
+}
+
+/// Routes that survive a reboot, parsed from the "Persistent Routes" section of `route print -4`
+#[tauri::command]
+pub async fn get_persistent_routes() -> Result<Vec<RouteEntry>, NetworkError> {
+    let output = run_cmd("route", &["print", "-4"])?;
+    Ok(parse_persistent_routes(&output))
+}
+
+/// Enable or disable the IPv6 (ms_tcpip6) binding on one adapter. Disabling it
+/// rebinds the adapter, so connectivity may drop for a few seconds.
+#[tauri::command]
+pub async fn set_ipv6_enabled(
+    interface_index: String,
+    enabled: bool,
+) -> Result<Ipv6BindingState, NetworkError> {
+    require_admin("Changing the IPv6 binding")?;
+
+    let idx = validate_interface_index(&interface_index)?;
+    let alias = interface_alias(&idx)?;
+    let verb = if enabled { "Enable" } else { "Disable" };
+    let script = format!(
+        r#"
+        {verb}-NetAdapterBinding -Name '{name}' -ComponentID ms_tcpip6 -ErrorAction Stop
+        (Get-NetAdapterBinding -Name '{name}' -ComponentID ms_tcpip6 -ErrorAction Stop).Enabled
+    "#,
+        name = ps_escape_single_quoted(&alias)
+    );
+    let output = run_powershell(&script)?;
+    let state = output
+        .lines()
+        .rev()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(|line| line.eq_ignore_ascii_case("True"))
+        .ok_or_else(|| NetworkError::parse("No IPv6 binding state returned"))?;
+
+    Ok(Ipv6BindingState {
+        interface_index: idx,
+        interface_alias: alias,
+        enabled: state,
+    })
+}
+
+/// Bounce a single adapter: disable, re-enable, then renew its DHCP lease.
+/// The per-NIC alternative to `repair_network_stack`.
+#[tauri::command]
+pub async fn reset_interface(interface_index: String) -> Result<InterfaceResetResult, NetworkError> {
+    require_admin("Resetting an interface")?;
+
+    let idx = validate_interface_index(&interface_index)?;
+    let alias = interface_alias(&idx)?;
+    let before_ips = interface_ipv4_addresses(Some(&idx));
+
+    let mut output_lines = vec![format!("Resetting {} (index {}).", alias, idx), String::new()];
+    let toggle_steps: [(&str, &[&str]); 2] = [
+        ("netsh", &["interface", "set", "interface", &alias, "admin=disabled"]),
+        ("netsh", &["interface", "set", "interface", &alias, "admin=enabled"]),
     ];
+    let (_, mut failed_count) = run_command_steps(&toggle_steps, &mut output_lines);
+
+    if wait_for_adapter_up(&idx, Duration::from_secs(15)) {
+        let renew_steps: [(&str, &[&str]); 1] = [("ipconfig", &["/renew", &alias])];
+        let (_, renew_failed) = run_command_steps(&renew_steps, &mut output_lines);
+        failed_count += renew_failed;
+    } else {
+        failed_count += 1;
+        output_lines.push(format!(
+            "[FAIL] {} did not come back up within 15s; DHCP renew skipped",
+            alias
+        ));
+        output_lines.push(String::new());
+    }
+
+    let after_ips = interface_ipv4_addresses(Some(&idx));
+    output_lines.push(format!("Before: {}", before_ips.join(", ")));
+    output_lines.push(format!("After: {}", after_ips.join(", ")));
+
+    Ok(InterfaceResetResult {
+        success: failed_count == 0,
+        interface_index: idx,
+        interface_alias: alias,
+        before_ips,
+        after_ips,
+        output: output_lines.join("\n"),
+    })
+}
+
+/// Fetch the NCSI connect-test page; a redirect or any other body means a captive portal
+#[tauri::command]
+pub async fn detect_captive_portal() -> Result<CaptivePortalStatus, NetworkError> {
+    let mut response = http_get(CAPTIVE_PORTAL_PROBE_URL, Duration::from_secs(5))?;
+    if (300..400).contains(&response.status) {
+        return Ok(CaptivePortalStatus {
+            captive: true,
+            redirect_url: response.headers.get("location").cloned(),
+        });
+    }
 
-    let if_idx;
-    if let Some(ref idx) = interface_index {
-        if !idx.is_empty() {
-            if_idx = idx.clone();
-            args.push("if");
-            args.push(&if_idx);
+    let mut body = response.body_prefix;
+    let mut chunk = [0u8; 4096];
+    while body.len() < 16 * 1024 {
+        match response.stream.read(&mut chunk) {
+            Ok(0) | Err(_) => break,
+            Ok(read) => body.extend_from_slice(&chunk[..read]),
         }
     }
+    let body = String::from_utf8_lossy(&body);
 
-    let result = run_cmd(args[0], &args[1..])?;
+    Ok(CaptivePortalStatus {
+        captive: response.status != 200 || body.trim() != CAPTIVE_PORTAL_EXPECTED_BODY,
+        redirect_url: None,
+    })
+}
 
-    Ok(CommandResult {
-        success: true,
-        output: result,
+/// Windows build, feature-update name (e.g. 23H2) and edition (e.g. Pro)
+#[tauri::command]
+pub async fn get_windows_version() -> Result<WindowsVersion, NetworkError> {
+    let script = r#"
+        $os = Get-CimInstance Win32_OperatingSystem
+        $current = Get-ItemProperty 'HKLM:\SOFTWARE\Microsoft\Windows NT\CurrentVersion' -ErrorAction SilentlyContinue
+        $display = $current.DisplayVersion
+        if (-not $display) { $display = $current.ReleaseId }
+        [PSCustomObject]@{
+            Caption = $os.Caption
+            BuildNumber = $os.BuildNumber
+            ProductType = [int]$os.ProductType
+            DisplayVersion = $display
+        } | ConvertTo-Json -Compress
+    "#;
+    let output = run_powershell(script)?;
+    let data: serde_json::Value = serde_json::from_str(output.trim())
+        .map_err(|e| NetworkError::parse(format!("JSON parse error: {}", e)))?;
+
+    let build = crate::detect_windows_build_number()
+        .or_else(|| data["BuildNumber"].as_str()?.trim().parse().ok())
+        .ok_or_else(|| NetworkError::parse("Could not read the Windows build number"))?;
+    // "Microsoft Windows 11 Pro" -> "Pro", "Microsoft Windows Server 2022 Standard" -> "Standard"
+    let edition = data["Caption"]
+        .as_str()
+        .unwrap_or("")
+        .split_whitespace()
+        .filter(|word| !matches!(*word, "Microsoft" | "Windows" | "Server"))
+        .filter(|word| !word.chars().all(|ch| ch.is_ascii_digit()))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    Ok(WindowsVersion {
+        build,
+        display_version: data["DisplayVersion"].as_str().unwrap_or("").to_string(),
+        edition,
+        // ProductType: 1 = workstation, 2 = domain controller, 3 = server
+        is_server: data["ProductType"].as_u64().is_some_and(|kind| kind != 1),
+    })
+}
+
+/// Running processes with working set and current CPU share, optionally filtered by name
+#[tauri::command]
+pub async fn get_processes(name_filter: Option<String>) -> Result<Vec<ProcessInfo>, NetworkError> {
+    let filter = name_filter.unwrap_or_default().trim().to_string();
+    if !filter
+        .chars()
+        .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, ' ' | '.' | '_' | '-'))
+    {
+        return Err(NetworkError::invalid(format!(
+            "Invalid process filter: {}",
+            filter
+        )));
+    }
+
+    let script = format!(
+        r#"
+        $filter = '*{}*'
+        $cores = [Math]::Max(1, [Environment]::ProcessorCount)
+        $cpu = @{{}}
+        Get-CimInstance Win32_PerfFormattedData_PerfProc_Process -ErrorAction SilentlyContinue |
+            Where-Object {{ $_.IDProcess -ne 0 }} |
+            ForEach-Object {{ $cpu[[int]$_.IDProcess] = [double]$_.PercentProcessorTime / $cores }}
+        Get-Process -ErrorAction SilentlyContinue |
+        Where-Object {{ $_.ProcessName -like $filter }} |
+        ForEach-Object {{
+            [PSCustomObject]@{{
+                Id = $_.Id
+                Name = $_.ProcessName
+                MemoryMb = [Math]::Round($_.WorkingSet64 / 1MB, 1)
+                Cpu = [Math]::Round([double]$cpu[$_.Id], 1)
+            }}
+        }} |
+        ConvertTo-Json -Compress
+    "#,
+        ps_escape_single_quoted(&filter)
+    );
+
+    let output = run_powershell(&script)?;
+    let mut processes: Vec<ProcessInfo> = parse_json_rows(&output)?
+        .iter()
+        .map(|item| ProcessInfo {
+            pid: item["Id"].as_u64().unwrap_or(0) as u32,
+            name: item["Name"].as_str().unwrap_or("").to_string(),
+            memory_mb: item["MemoryMb"].as_f64().unwrap_or(0.0),
+            cpu_percent: item["Cpu"].as_f64().unwrap_or(0.0),
+        })
+        .collect();
+    processes.sort_by(|a, b| b.memory_mb.total_cmp(&a.memory_mb));
+    Ok(processes)
+}
+
+/// Force-stop a process by PID. Refuses the Idle (0) and System (4) processes and this app itself.
+#[tauri::command]
+pub async fn kill_process(pid: u32) -> Result<CommandResult, NetworkError> {
+    if pid == 0 || pid == 4 {
+        return Err(NetworkError::invalid(format!(
+            "PID {} is a system-critical process",
+            pid
+        )));
+    }
+    if pid == std::process::id() {
+        return Err(NetworkError::invalid("Refusing to stop Super Route Pro itself"));
+    }
+
+    let script = format!(
+        "$p = Get-Process -Id {pid} -ErrorAction Stop; Stop-Process -Id {pid} -Force -ErrorAction Stop; $p.ProcessName"
+    );
+    match run_powershell(&script) {
+        Ok(name) => Ok(CommandResult {
+            success: true,
+            output: format!("[OK] Stopped {} (PID {})", name.trim(), pid),
+        }),
+        Err(e) => Ok(CommandResult {
+            success: false,
+            output: format!("[FAIL] Stop PID {}: {}", pid, e),
+        }),
+    }
+}
+
+/// Last boot time (ISO 8601, local offset) and seconds since then
+#[tauri::command]
+pub async fn get_system_uptime() -> Result<SystemUptime, NetworkError> {
+    // CIM already converts the WMI "yyyyMMddHHmmss.ffffff+UUU" string to a DateTime
+    let script = r#"
+        $boot = (Get-CimInstance Win32_OperatingSystem).LastBootUpTime
+        [PSCustomObject]@{
+            LastBoot = $boot.ToString('yyyy-MM-ddTHH:mm:sszzz')
+            UptimeSecs = [int64]((Get-Date) - $boot).TotalSeconds
+        } | ConvertTo-Json -Compress
+    "#;
+    let output = run_powershell(script)?;
+    let data: serde_json::Value = serde_json::from_str(output.trim())
+        .map_err(|e| NetworkError::parse(format!("JSON parse error: {}", e)))?;
+
+    Ok(SystemUptime {
+        last_boot: data["LastBoot"].as_str().unwrap_or("").to_string(),
+        uptime_secs: data["UptimeSecs"].as_u64().unwrap_or(0),
     })
 }
 
-/// Delete a route
+/// Size and free space per drive letter. Only fixed disks unless `include_all` is set
+/// (then removable and network drives are listed too).
+#[tauri::command]
+pub async fn get_disk_usage(include_all: Option<bool>) -> Result<Vec<DiskInfo>, NetworkError> {
+    // Win32_LogicalDisk DriveType: 2 = removable, 3 = fixed, 4 = network
+    let drive_types = if include_all.unwrap_or(false) {
+        "2,3,4"
+    } else {
+        "3"
+    };
+    let script = format!(
+        r#"
+        Get-CimInstance Win32_LogicalDisk -ErrorAction SilentlyContinue |
+        Where-Object {{ @({drive_types}) -contains $_.DriveType -and $_.Size }} |
+        ForEach-Object {{
+            [PSCustomObject]@{{
+                Drive = $_.DeviceID
+                Size = [uint64]$_.Size
+                Free = [uint64]$_.FreeSpace
+            }}
+        }} |
+        ConvertTo-Json -Compress
+    "#
+    );
+
+    let disks = parse_json_rows(&run_powershell(&script)?)?
+        .iter()
+        .map(|item| {
+            let total_bytes = item["Size"].as_u64().unwrap_or(0);
+            let free_bytes = item["Free"].as_u64().unwrap_or(0).min(total_bytes);
+            let used_percent = if total_bytes == 0 {
+                0.0
+            } else {
+                (total_bytes - free_bytes) as f32 * 100.0 / total_bytes as f32
+            };
+            DiskInfo {
+                drive: item["Drive"].as_str().unwrap_or("").to_string(),
+                total_bytes,
+                free_bytes,
+                used_percent,
+            }
+        })
+        .collect();
+    Ok(disks)
+}
+
+/// Every cache-cleanup target with the folders/files it deletes
 #[tauri::command]
-pub async fn delete_route(destination: String, mask: String) -> Result<CommandResult, String> {
-    let result = run_cmd("route", &["delete", &destination, "mask", &mask])?;
-    Ok(CommandResult {
-        success: true,
-        output: result,
-    })
+pub async fn get_cache_target_details() -> Result<Vec<CacheTargetInfo>, NetworkError> {
+    Ok(CACHE_RECIPES
+        .iter()
+        .map(|recipe| CacheTargetInfo {
+            id: recipe.id.to_string(),
+            label: recipe.label.to_string(),
+            description: recipe.description.to_string(),
+            paths: recipe.display_paths(),
+        })
+        .collect())
 }
 
-/// Flush all routes
+/// Subnet math for the route editor: network, broadcast and usable host range
 #[tauri::command]
-pub async fn flush_routes() -> Result<CommandResult, String> {
-    let result = run_cmd("route", &["-f"])?;
-    Ok(CommandResult {
-        success: true,
-        output: result,
-    })
+pub async fn compute_subnet_info(ip: String, mask: String) -> Result<SubnetInfo, NetworkError> {
+    subnet_info(&ip, &mask)
 }
 
-/// Set a NIC as default internet gateway
+/// Saved user preferences (defaults when nothing has been saved yet)
 #[tauri::command]
-pub async fn set_default_gateway(
-    gateway: String,
-    interface_index: String,
-) -> Result<CommandResult, String> {
-    // Raise metric of all existing default routes
-    let _ = run_powershell("Set-NetRoute -DestinationPrefix 0.0.0.0/0 -RouteMetric 500");
-
-    // Delete existing default route
-    let _ = run_cmd("route", &["delete", "0.0.0.0"]);
+pub async fn get_preferences(app: tauri::AppHandle) -> Result<Preferences, NetworkError> {
+    Ok(load_preferences(&app))
+}
 
-    // Add new default route with low metric
-    let result = run_cmd(
-        "route",
-        &[
-            "-p",
-            "add",
-            "0.0.0.0",
-            "mask",
-            "0.0.0.0",
-            &gateway,
-            "metric",
-            "1",
-            "if",
-            &interface_index,
-        ],
-    )?;
+/// Validate and save user preferences, returning what was stored
+#[tauri::command]
+pub async fn set_preferences(
+    app: tauri::AppHandle,
+    prefs: Preferences,
+) -> Result<Preferences, NetworkError> {
+    if !(200..=15_000).contains(&prefs.ping_timeout_ms) {
+        return Err(NetworkError::invalid(format!(
+            "ping_timeout_ms must be between 200 and 15000, got {}",
+            prefs.ping_timeout_ms
+        )));
+    }
+    if let Some(workers) = prefs.fping_max_parallel {
+        if !(1..=256).contains(&workers) {
+            return Err(NetworkError::invalid(format!(
+                "fping_max_parallel must be between 1 and 256, got {}",
+                workers
+            )));
+        }
+    }
+    let prefs = Preferences {
+        default_interface_index: match prefs.default_interface_index.as_deref().map(str::trim) {
+            Some(idx) if !idx.is_empty() => Some(validate_interface_index(idx)?),
+            _ => None,
+        },
+        ..prefs
+    };
 
-    Ok(CommandResult {
-        success: true,
-        output: result,
-    })
+    let path = app_data_dir(&app)?.join(PREFERENCES_FILE);
+    let json = serde_json::to_string_pretty(&prefs)
+        .map_err(|e| NetworkError::parse(format!("Failed to serialize preferences: {}", e)))?;
+    fs::write(&path, json).map_err(|e| {
+        NetworkError::io(format!(
+            "Failed to write preferences file: {} ({})",
+            path.display(),
+            e
+        ))
+    })?;
+    Ok(prefs)
 }
 
-/// Run a network fix command (flush DNS, renew IP, etc.)
+/// Dry run for `set_default_gateway`: is the gateway on the interface's subnet and
+/// does it answer a ping? Nothing is changed.
 #[tauri::command]
-pub async fn run_network_command(command: String) -> Result<CommandResult, String> {
-    // Whitelist of allowed commands for security
-    let allowed_prefixes = [
-        "ipconfig",
-        "ipconfig /displaydns",
-        "powercfg /batteryreport",
-        "tracert",
-        "nslookup",
-        "netsh wlan show interface",
-        "netsh winhttp reset proxy",
-        "netsh int ip reset",
-        "netsh winsock reset",
-        "netsh interface ip delete arpcache",
-        "netsh advfirewall reset",
-        "powershell -noprofile -command get-netadapter",
-        "powershell -noprofile -command test-netconnection",
-    ];
+pub async fn preview_default_gateway_change(
+    gateway: String,
+    interface_index: String,
+) -> Result<GatewayPreview, NetworkError> {
+    let idx = validate_interface_index(&interface_index)?;
+    let gateway = gateway.trim().to_string();
+    validate_ipv4(&gateway, "gateway")?;
 
-    let cmd_lower = command.to_lowercase();
-    if !allowed_prefixes
-        .iter()
-        .any(|prefix| cmd_lower.starts_with(prefix))
-    {
-        return Err("Command not allowed".to_string());
+    let mut warnings = Vec::new();
+    let mut on_link = false;
+    let ipv4_addresses: Vec<InterfaceAddress> = get_interface_addresses(idx.clone())
+        .await?
+        .into_iter()
+        .filter(|addr| addr.address_family == "IPv4")
+        .collect();
+    if ipv4_addresses.is_empty() {
+        warnings.push(format!("Interface {} has no IPv4 address", idx));
+    }
+    for addr in &ipv4_addresses {
+        let subnet = subnet_info(&addr.ip, &addr.prefix_length.to_string())?;
+        let gateway_subnet = subnet_info(&gateway, &addr.prefix_length.to_string())?;
+        if subnet.network != gateway_subnet.network {
+            continue;
+        }
+        on_link = true;
+        if gateway == addr.ip {
+            warnings.push(format!("{} is this interface's own address", gateway));
+        } else if addr.prefix_length < 31
+            && (gateway == subnet.network || gateway == subnet.broadcast)
+        {
+            warnings.push(format!(
+                "{} is the network or broadcast address of {}/{}",
+                gateway, subnet.network, addr.prefix_length
+            ));
+        }
+    }
+    if !on_link && !ipv4_addresses.is_empty() {
+        warnings.push(format!(
+            "{} is not on any subnet of interface {}; Windows will not use it as a next hop",
+            gateway, idx
+        ));
     }
 
-    let output = Command::new("cmd")
-        .args(["/C", &command])
-        .creation_flags(CREATE_NO_WINDOW)
-        .output()
-        .map_err(|e| format!("Failed to run command: {}", e))?;
+    let ping = ping_once_target(gateway.clone(), "2000", 1);
+    if !ping.success {
+        warnings.push(format!(
+            "{} did not answer a ping (some routers drop ICMP)",
+            gateway
+        ));
+    }
 
-    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+    if let Ok(current) = read_default_gateways() {
+        if let Some(active) = current.first() {
+            if active.interface_index != idx {
+                warnings.push(format!(
+                    "Traffic currently leaves via {} (interface {}); this change moves it",
+                    active.interface_alias, active.interface_index
+                ));
+            }
+        }
+    }
 
-    Ok(CommandResult {
-        success: output.status.success(),
-        output: if stdout.is_empty() { stderr } else { stdout },
+    Ok(GatewayPreview {
+        on_link,
+        reachable: ping.success,
+        latency_ms: ping.success.then_some(ping.latency_ms),
+        warnings,
     })
 }
 
-/// Ping a host and return latency
+/// Flush the ARP cache of one interface, or every interface when no index is given
 #[tauri::command]
-pub async fn ping_host(target: String, count: Option<u32>) -> Result<PingResult, String> {
-    let n = count.unwrap_or(1).to_string();
+pub async fn flush_arp_cache(interface_index: Option<String>) -> Result<ArpFlushResult, NetworkError> {
+    require_admin("Flushing the ARP cache")?;
 
-    let start = Instant::now();
-    let output = Command::new("ping")
-        .args(["-n", &n, "-w", "2000", &target])
-        .creation_flags(CREATE_NO_WINDOW)
-        .output()
-        .map_err(|e| format!("Ping failed: {}", e))?;
+    let index = match interface_index.as_deref().map(str::trim) {
+        Some(idx) if !idx.is_empty() => Some(validate_interface_index(idx)?),
+        _ => None,
+    };
+    let before = count_arp_entries(index.as_deref());
 
-    let elapsed = start.elapsed().as_millis() as u32;
-    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    let mut output_lines = Vec::new();
+    let failed_count = match index {
+        Some(ref idx) => {
+            let script = format!(
+                "Remove-NetNeighbor -InterfaceIndex {} -AddressFamily IPv4 -Confirm:$false -ErrorAction Stop",
+                idx
+            );
+            match run_powershell(&script) {
+                Ok(_) => {
+                    output_lines.push(format!("[OK] ARP cache flushed for interface {}", idx));
+                    0
+                }
+                Err(e) => {
+                    output_lines.push(format!(
+                        "[FAIL] ARP flush for interface {}: {}",
+                        idx,
+                        e.to_string().trim()
+                    ));
+                    1
+                }
+            }
+        }
+        None => {
+            let steps: [(&str, &[&str]); 1] =
+                [("netsh", &["interface", "ip", "delete", "arpcache"])];
+            run_command_steps(&steps, &mut output_lines).1
+        }
+    };
 
-    let latency = parse_ping_latency(&stdout, elapsed);
+    // Entries re-learned right after the flush are new, so report what was there before.
+    let removed = if failed_count == 0 { before.unwrap_or(0) } else { 0 };
+    output_lines.push(format!("Removed {} entr{}", removed, if removed == 1 { "y" } else { "ies" }));
 
-    Ok(PingResult {
-        success: stdout.contains("Reply from") || stdout.contains("time="),
-        latency_ms: latency,
-        output: stdout,
+    Ok(ArpFlushResult {
+        success: failed_count == 0,
+        removed,
+        output: output_lines.join("\n"),
     })
 }
 
-/// Get bloatware candidates and installation status
+/// TCP connections grouped by owning process, busiest first
 #[tauri::command]
-pub async fn get_bloatware_candidates() -> Result<Vec<BloatwareItem>, String> {
-    let ps_script = r#"
-        $names = @()
-        try {
-            $names += Get-AppxPackage -AllUsers -ErrorAction Stop | Select-Object -ExpandProperty Name
-        } catch {
-            $names += Get-AppxPackage -ErrorAction SilentlyContinue | Select-Object -ExpandProperty Name
-        }
-        try {
-            $names += Get-AppxProvisionedPackage -Online -ErrorAction Stop | Select-Object -ExpandProperty DisplayName
-        } catch {}
-        $names |
-            Where-Object { $_ -and $_.Trim().Length -gt 0 } |
-            ForEach-Object { $_.ToLowerInvariant() } |
-            Sort-Object -Unique |
-            ConvertTo-Json -Compress
+pub async fn get_process_network_usage() -> Result<Vec<ProcessNetUsage>, NetworkError> {
+    // One Get-Process call for the whole table instead of one per connection
+    let script = r#"
+        $names = @{}
+        Get-Process -ErrorAction SilentlyContinue | ForEach-Object { $names[$_.Id] = $_.ProcessName }
+        Get-NetTCPConnection -ErrorAction SilentlyContinue |
+        ForEach-Object {
+            [PSCustomObject]@{
+                Pid = [int]$_.OwningProcess
+                Name = $names[[int]$_.OwningProcess]
+                State = $_.State.ToString()
+                LocalPort = [int]$_.LocalPort
+            }
+        } |
+        ConvertTo-Json -Compress
     "#;
 
-    let output = run_powershell(ps_script)?;
-    let mut installed = HashSet::new();
-    let parsed = serde_json::from_str::<serde_json::Value>(output.trim())
-        .unwrap_or(serde_json::Value::Array(vec![]));
-
-    match parsed {
-        serde_json::Value::Array(arr) => {
-            for item in arr {
-                if let Some(name) = item.as_str() {
-                    installed.insert(name.to_lowercase());
+    let mut usage: HashMap<u32, ProcessNetUsage> = HashMap::new();
+    for item in parse_json_rows(&run_powershell(script)?)? {
+        let pid = item["Pid"].as_u64().unwrap_or(0) as u32;
+        let entry = usage.entry(pid).or_insert_with(|| ProcessNetUsage {
+            pid,
+            process_name: item["Name"].as_str().unwrap_or("").to_string(),
+            connection_count: 0,
+            listening_ports: Vec::new(),
+            established_count: 0,
+        });
+        entry.connection_count += 1;
+        match item["State"].as_str().unwrap_or("") {
+            "Listen" => {
+                let port = item["LocalPort"].as_u64().unwrap_or(0) as u16;
+                if !entry.listening_ports.contains(&port) {
+                    entry.listening_ports.push(port);
                 }
             }
+            "Established" => entry.established_count += 1,
+            _ => {}
         }
-        serde_json::Value::String(single_name) => {
-            installed.insert(single_name.to_lowercase());
-        }
-        _ => {}
     }
 
-    let mut items: Vec<BloatwareItem> = BLOATWARE_CANDIDATES
-        .iter()
-        .map(|(package_name, label)| BloatwareItem {
-            package_name: (*package_name).to_string(),
-            label: (*label).to_string(),
-            installed: installed.contains(&package_name.to_lowercase()),
-        })
-        .collect();
-    items.sort_by(|a, b| a.label.cmp(&b.label));
-    Ok(items)
+    let mut usage: Vec<ProcessNetUsage> = usage.into_values().collect();
+    for entry in &mut usage {
+        entry.listening_ports.sort_unstable();
+    }
+    usage.sort_by(|a, b| {
+        b.connection_count
+            .cmp(&a.connection_count)
+            .then(a.pid.cmp(&b.pid))
+    });
+    Ok(usage)
 }
 
-/// Remove selected bloatware packages
+/// Add a non-persistent route that deletes itself after `ttl_secs` (5..=86400) and
+/// then emits `temp-route-expired` with the route
 #[tauri::command]
-pub async fn remove_bloatware(packages: Vec<String>) -> Result<CommandResult, String> {
-    if packages.is_empty() {
-        return Err("No packages selected".to_string());
-    }
+pub async fn add_temporary_route(
+    app: tauri::AppHandle,
+    destination: String,
+    mask: String,
+    gateway: String,
+    ttl_secs: u32,
+) -> Result<TemporaryRoute, NetworkError> {
+    require_admin("Adding a route")?;
 
-    let allowed: HashMap<String, &str> = BLOATWARE_CANDIDATES
-        .iter()
-        .map(|(package_name, _)| (package_name.to_lowercase(), *package_name))
-        .collect();
+    validate_ipv4(&destination, "route destination")?;
+    mask_to_prefix(&mask)?;
+    validate_ipv4(&gateway, "gateway")?;
+    let ttl_secs = ttl_secs.clamp(5, 86_400);
+    let route = TemporaryRoute {
+        id: format!("{}/{}@{}", destination.trim(), mask.trim(), gateway.trim()),
+        destination: destination.trim().to_string(),
+        mask: mask.trim().to_string(),
+        gateway: gateway.trim().to_string(),
+        expires_at: unix_timestamp() + ttl_secs as u64,
+    };
 
-    let mut selected = Vec::new();
-    let mut seen = HashSet::new();
-    for raw in packages {
-        let trimmed = raw.trim();
-        if trimmed.is_empty() {
-            continue;
-        }
+    // No -p: the route is gone after a reboot even if the expiry never runs
+    let result = run_cmd(
+        "route",
+        &["add", &route.destination, "mask", &route.mask, &route.gateway],
+    );
+    log_route_mutation(
+        &app,
+        "add_temporary_route",
+        serde_json::json!({ "route": route, "ttl_secs": ttl_secs }),
+        &result,
+    );
+    result?;
 
-        let lower = trimmed.to_lowercase();
-        let is_safe_token = lower
-            .chars()
-            .all(|ch| ch.is_ascii_alphanumeric() || ch == '.' || ch == '_' || ch == '-');
-        if !is_safe_token {
-            continue;
+    let stop = Arc::new(AtomicBool::new(false));
+    {
+        let mut registry = match TEMP_ROUTES.lock() {
+            Ok(g) => g,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        // Re-adding the same route restarts its timer
+        if let Some((_, previous)) =
+            registry.insert(route.id.clone(), (route.clone(), Arc::clone(&stop)))
+        {
+            previous.store(true, Ordering::Relaxed);
         }
+    }
 
-        if let Some(canonical) = allowed.get(&lower) {
-            let canonical_name = (*canonical).to_string();
-            if seen.insert(canonical_name.clone()) {
-                selected.push(canonical_name);
-            }
+    let expiring = route.clone();
+    thread::spawn(move || {
+        sleep_unless_stopped(Duration::from_secs(ttl_secs as u64), &stop);
+        if stop.load(Ordering::Relaxed) {
+            return;
+        }
+        {
+            let mut registry = match TEMP_ROUTES.lock() {
+                Ok(g) => g,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+            registry.remove(&expiring.id);
         }
+        let result = delete_temporary_route(&expiring);
+        log_route_mutation(
+            &app,
+            "temp_route_expired",
+            serde_json::json!(expiring),
+            &result,
+        );
+        let _ = app.emit("temp-route-expired", &expiring);
+    });
+
+    Ok(route)
+}
+
+/// Remove a temporary route now instead of waiting for it to expire
+#[tauri::command]
+pub async fn cancel_temporary_route(
+    app: tauri::AppHandle,
+    id: String,
+) -> Result<CommandResult, NetworkError> {
+    require_admin("Deleting a route")?;
+
+    let (route, stop) = {
+        let mut registry = match TEMP_ROUTES.lock() {
+            Ok(g) => g,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        registry
+            .remove(&id)
+            .ok_or_else(|| NetworkError::not_found(format!("No temporary route {}", id)))?
+    };
+    stop.store(true, Ordering::Relaxed);
+
+    let result = delete_temporary_route(&route);
+    log_route_mutation(
+        &app,
+        "cancel_temporary_route",
+        serde_json::json!(route),
+        &result,
+    );
+    Ok(CommandResult {
+        success: true,
+        output: result?,
+    })
+}
+
+/// Structured DNS lookup via `Resolve-DnsName` (A + AAAA, following CNAMEs)
+#[tauri::command]
+pub async fn resolve_host(name: String) -> Result<HostResolution, NetworkError> {
+    let name = name.trim().trim_end_matches('.').to_string();
+    if !is_safe_host_token(&name) {
+        return Err(NetworkError::invalid(format!("Invalid host name: {}", name)));
     }
 
-    if selected.is_empty() {
-        return Err("No valid bloatware packages selected".to_string());
+    let script = format!(
+        r#"
+        Resolve-DnsName -Name '{name}' -ErrorAction Stop |
+        Where-Object {{ $_.Section -eq 'Answer' }} |
+        ForEach-Object {{
+            [PSCustomObject]@{{
+                Type = $_.Type.ToString()
+                Name = $_.Name
+                Address = $_.IPAddress
+                NameHost = $_.NameHost
+            }}
+        }} |
+        ConvertTo-Json -Compress
+    "#
+    );
+    let output = run_powershell(&script).map_err(|e| {
+        NetworkError::not_found(format!("Could not resolve {}: {}", name, e.to_string().trim()))
+    })?;
+
+    let mut ipv4_addresses = Vec::new();
+    let mut ipv6_addresses = Vec::new();
+    let mut canonical_name = None;
+    for record in parse_json_rows(&output)? {
+        let address = record["Address"].as_str().unwrap_or("").to_string();
+        match record["Type"].as_str().unwrap_or("") {
+            "A" if !ipv4_addresses.contains(&address) => ipv4_addresses.push(address),
+            "AAAA" if !ipv6_addresses.contains(&address) => ipv6_addresses.push(address),
+            // The last CNAME in the chain points at the name that holds the addresses
+            "CNAME" => canonical_name = record["NameHost"].as_str().map(str::to_string),
+            _ => {}
+        }
     }
 
-    let mut output_lines = vec![
-        format!("Requested removal for {} package(s).", selected.len()),
-        "Administrative privileges may be required for removal.".to_string(),
-        String::new(),
-    ];
-    let mut removed = 0u32;
-    let mut skipped = 0u32;
-    let mut failed = 0u32;
+    let addresses = ipv4_addresses.iter().chain(&ipv6_addresses).cloned().collect();
+    Ok(HostResolution {
+        name,
+        addresses,
+        ipv4_addresses,
+        ipv6_addresses,
+        canonical_name,
+    })
+}
 
-    for package_name in selected {
-        let escaped_name = ps_escape_single_quoted(&package_name);
-        let script = format!(
-            r#"
-$pkgName = '{escaped_name}'
-$hasFailure = $false
-$removedInstalled = 0
-$removedProvisioned = 0
+/// Check every IPv4 route for a missing interface or an unreachable gateway
+#[tauri::command]
+pub async fn audit_routes() -> Result<Vec<RouteAuditResult>, NetworkError> {
+    let routes = read_routing_table(None)?;
+    let interfaces: HashSet<String> = run_powershell(
+        "Get-NetIPInterface -AddressFamily IPv4 -ErrorAction SilentlyContinue | Select-Object -ExpandProperty InterfaceIndex",
+    )?
+    .lines()
+    .map(|line| line.trim().to_string())
+    .filter(|line| !line.is_empty())
+    .collect();
 
-$installedMatches = Get-AppxPackage -AllUsers -ErrorAction SilentlyContinue | Where-Object {{ $_.Name -eq $pkgName }}
-if (-not $installedMatches) {{
-  $installedMatches = Get-AppxPackage -ErrorAction SilentlyContinue | Where-Object {{ $_.Name -eq $pkgName }}
-}}
-foreach ($pkg in $installedMatches) {{
-  try {{
-    Remove-AppxPackage -Package $pkg.PackageFullName -AllUsers -ErrorAction Stop | Out-Null
-    $removedInstalled++
-  }} catch {{
-    $hasFailure = $true
-    Write-Output "[FAIL] $pkgName installed remove error: $($_.Exception.Message)"
-  }}
-}}
+    let is_on_link = |gateway: &str| gateway.is_empty() || gateway == "0.0.0.0";
+    let gateways: Vec<String> = routes
+        .iter()
+        .map(|route| route.gateway.clone())
+        .filter(|gateway| !is_on_link(gateway))
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    let reachable: HashMap<String, bool> = gateways
+        .iter()
+        .cloned()
+        .zip(run_ordered_pool(gateways.clone(), 16, |gateway| {
+            ping_once_target(gateway, "1000", 1).success
+        }))
+        .collect();
 
-try {{
-  $provisionedMatches = Get-AppxProvisionedPackage -Online -ErrorAction Stop | Where-Object {{ $_.DisplayName -eq $pkgName }}
-  foreach ($prov in $provisionedMatches) {{
-    try {{
-      Remove-AppxProvisionedPackage -Online -PackageName $prov.PackageName -ErrorAction Stop | Out-Null
-      $removedProvisioned++
-    }} catch {{
-      $hasFailure = $true
-      Write-Output "[FAIL] $pkgName provisioned remove error: $($_.Exception.Message)"
-    }}
-  }}
-}} catch {{
-  $hasFailure = $true
-  Write-Output "[FAIL] $pkgName provisioned query error: $($_.Exception.Message)"
-}}
+    Ok(routes
+        .into_iter()
+        .map(|route| {
+            let interface_exists = interfaces.contains(&route.interface_index);
+            let gateway_reachable = if is_on_link(&route.gateway) {
+                None
+            } else {
+                reachable.get(&route.gateway).copied()
+            };
+            let issue = if !interface_exists {
+                Some(format!("Interface {} no longer exists", route.interface_index))
+            } else if gateway_reachable == Some(false) {
+                Some(format!("Gateway {} did not answer a ping", route.gateway))
+            } else {
+                None
+            };
+            RouteAuditResult {
+                route,
+                interface_exists,
+                gateway_reachable,
+                issue,
+            }
+        })
+        .collect())
+}
+
+/// Resolve `domain` once per second for `duration_secs`, emitting `dns-monitor-sample`
+/// events. Queries go to the first configured DNS server so cached answers don't hide failures.
+/// Runs on the blocking pool; `stop_dns_monitor` ends it early.
+#[tauri::command(async)]
+pub fn monitor_dns(
+    domain: String,
+    duration_secs: u32,
+    window: tauri::Window,
+) -> Result<DnsMonitorReport, NetworkError> {
+    let domain = domain.trim().to_string();
+    if !is_safe_host_token(&domain) {
+        return Err(NetworkError::invalid(format!("Invalid domain: {}", domain)));
+    }
+    let duration_secs = duration_secs.clamp(1, 3600);
+    let server = run_powershell(
+        "Get-DnsClientServerAddress -AddressFamily IPv4 -ErrorAction SilentlyContinue | Where-Object { $_.ServerAddresses } | Select-Object -First 1 -ExpandProperty ServerAddresses | Select-Object -First 1",
+    )
+    .ok()
+    .map(|out| out.trim().to_string())
+    .filter(|s| s.parse::<std::net::IpAddr>().is_ok());
 
-if ($removedInstalled -gt 0 -or $removedProvisioned -gt 0) {{
-  Write-Output "[OK] $pkgName removed installed=$removedInstalled provisioned=$removedProvisioned"
-}} elseif ($hasFailure) {{
-  Write-Output "[WARN] $pkgName no removal completed"
-}} else {{
-  Write-Output "[SKIP] $pkgName not installed"
-}}
-"#
-        );
+    let mut attempts = 0u32;
+    let mut successes = 0u32;
+    let mut latency_total = 0u64;
+    let mut failures = Vec::new();
+    let stop_flag = start_monitor_run(&DNS_MONITOR);
 
-        match run_powershell(&script) {
-            Ok(script_output) => {
-                let clean_output = script_output.trim();
-                if clean_output.is_empty() {
-                    skipped += 1;
-                    output_lines.push(format!("[SKIP] {} no output returned", package_name));
-                } else {
-                    output_lines.extend(clean_output.lines().map(|line| line.trim_end().to_string()));
-                    let has_fail = clean_output.contains("[FAIL]");
-                    let has_ok = clean_output.contains("[OK]");
-                    let has_skip = clean_output.contains("[SKIP]");
-                    if has_fail {
-                        failed += 1;
-                    } else if has_ok {
-                        removed += 1;
-                    } else if has_skip {
-                        skipped += 1;
-                    } else {
-                        skipped += 1;
-                    }
+    for second in 0..duration_secs {
+        if stop_flag.load(Ordering::Relaxed) {
+            break;
+        }
+        let tick_start = Instant::now();
+        let lookup = timed_dns_lookup(&domain, server.as_deref());
+        attempts += 1;
+        let sample = match lookup {
+            Ok(latency_ms) => {
+                successes += 1;
+                latency_total += latency_ms as u64;
+                DnsMonitorSample {
+                    second,
+                    success: true,
+                    latency_ms,
+                    error: None,
                 }
             }
-            Err(err) => {
-                failed += 1;
-                output_lines.push(format!(
-                    "[FAIL] {} command execution failed: {}",
-                    package_name,
-                    err.trim()
-                ));
+            Err(e) => {
+                let error = e.to_string().trim().to_string();
+                failures.push(format!("{}s: {}", second, error));
+                DnsMonitorSample {
+                    second,
+                    success: false,
+                    latency_ms: 0,
+                    error: Some(error),
+                }
             }
+        };
+        if window.emit("dns-monitor-sample", &sample).is_err() {
+            break;
         }
 
-        output_lines.push(String::new());
+        if let Some(rest) = Duration::from_secs(1).checked_sub(tick_start.elapsed()) {
+            sleep_unless_stopped(rest, &stop_flag);
+        }
     }
+    finish_monitor_run(&DNS_MONITOR, &stop_flag);
 
-    output_lines.push(format!(
-        "Summary: removed={} skipped={} failed={}",
-        removed, skipped, failed
-    ));
-
-    Ok(CommandResult {
-        success: failed == 0,
-        output: output_lines.join("\n"),
+    Ok(DnsMonitorReport {
+        attempts,
+        successes,
+        avg_latency_ms: if successes == 0 {
+            0
+        } else {
+            (latency_total / successes as u64) as u32
+        },
+        failures,
     })
 }
 
-/// Generate and return battery report HTML for in-app preview
+/// Stop the running `monitor_dns`; returns false when none was running
 #[tauri::command]
-pub async fn get_battery_report() -> Result<BatteryReportResult, String> {
-    let report_path = std::env::temp_dir().join("SuperRoutePro-BatteryReport.html");
-    let report_path_arg = report_path.to_string_lossy().to_string();
+pub async fn stop_dns_monitor() -> Result<bool, NetworkError> {
+    Ok(stop_monitor_run(&DNS_MONITOR))
+}
 
-    let _ = run_cmd(
-        "powercfg",
-        &["/batteryreport", "/output", &report_path_arg],
-    )?;
+/// Recent errors and warnings from the TCP/IP, DHCP and DNS client providers in the
+/// System log, most recent first. `max_entries` is capped at 500.
+#[tauri::command]
+pub async fn get_network_event_log(max_entries: u32) -> Result<Vec<EventLogEntry>, NetworkError> {
+    let max_entries = max_entries.clamp(1, 500);
+    let providers = NETWORK_EVENT_PROVIDERS
+        .iter()
+        .map(|p| format!("'{}'", p))
+        .collect::<Vec<_>>()
+        .join(",");
+    // Queried one provider at a time: Get-WinEvent fails the whole filter when any
+    // provider is missing or has no events. Level 1-3 = Critical, Error, Warning.
+    let script = format!(
+        r#"
+        $events = foreach ($provider in @({providers})) {{
+            Get-WinEvent -FilterHashtable @{{ LogName = 'System'; ProviderName = $provider; Level = 1,2,3 }} -MaxEvents {max_entries} -ErrorAction SilentlyContinue
+        }}
+        $events | Sort-Object TimeCreated -Descending | Select-Object -First {max_entries} |
+        ForEach-Object {{
+            [PSCustomObject]@{{
+                Time = $_.TimeCreated.ToString('yyyy-MM-ddTHH:mm:sszzz')
+                Level = $_.LevelDisplayName
+                Provider = $_.ProviderName
+                Id = [int]$_.Id
+                Message = if ($_.Message) {{ ($_.Message -split "`r?`n")[0] }} else {{ '' }}
+            }}
+        }} |
+        ConvertTo-Json -Compress
+    "#
+    );
 
-    let html = fs::read_to_string(&report_path).map_err(|e| {
-        format!(
-            "Failed to read battery report file: {} ({})",
-            report_path_arg, e
+    let entries = parse_json_rows(&run_powershell(&script)?)?
+        .iter()
+        .map(|item| EventLogEntry {
+            time: item["Time"].as_str().unwrap_or("").to_string(),
+            level: item["Level"].as_str().unwrap_or("").to_string(),
+            provider: item["Provider"].as_str().unwrap_or("").to_string(),
+            event_id: item["Id"].as_u64().unwrap_or(0) as u32,
+            message: item["Message"].as_str().unwrap_or("").trim().to_string(),
+        })
+        .collect();
+    Ok(entries)
+}
+
+/// Diagnostic path-MTU probe: walks common MTUs downwards with don't-fragment pings,
+/// then narrows down between the first size that passed and the last that failed.
+/// Changes nothing (see `find_optimal_mtu` to apply a value).
+#[tauri::command]
+pub async fn test_path_mtu(target: String) -> Result<PathMtuResult, NetworkError> {
+    let target = target.trim().to_string();
+    if !is_safe_host_token(&target) {
+        return Err(NetworkError::invalid(format!("Invalid target: {}", target)));
+    }
+
+    let mut probes = Vec::new();
+    let mut probe = |mtu: u32| {
+        let ok = ping_dont_fragment(&target, mtu - ICMP_OVERHEAD);
+        probes.push((mtu, ok));
+        ok
+    };
+
+    let mut failed_above = None;
+    let mut passed = None;
+    for mtu in MTU_LADDER {
+        if probe(mtu) {
+            passed = Some(mtu);
+            break;
+        }
+        failed_above = Some(mtu);
+    }
+    let mut low = passed.ok_or_else(|| {
+        NetworkError::command_failed(
+            "ping",
+            format!(
+                "{} did not answer don't-fragment pings at any size down to {}",
+                target,
+                MTU_LADDER[MTU_LADDER.len() - 1]
+            ),
         )
     })?;
-
-    if html.trim().is_empty() {
-        return Err("Battery report is empty".to_string());
+    if let Some(failed) = failed_above {
+        let mut high = failed - 1;
+        while low < high {
+            let mid = (low + high).div_ceil(2);
+            if probe(mid) {
+                low = mid;
+            } else {
+                high = mid - 1;
+            }
+        }
     }
 
-    Ok(BatteryReportResult { html })
+    Ok(PathMtuResult {
+        path_mtu: low,
+        fragmentation_occurred: low < MTU_LADDER[0],
+        probes,
+    })
 }
 
-/// Clear selected system/browser cache targets
+/// DNS servers in the order Windows tries them: connected interfaces with servers,
+/// lowest interface metric first (how a VPN adapter ends up overriding the LAN's DNS)
 #[tauri::command]
-pub async fn clear_cache_targets(targets: Vec<String>) -> Result<CommandResult, String> {
-    if targets.is_empty() {
-        return Err("No cache targets selected".to_string());
-    }
-
-    let mut selected: Vec<(String, &'static str, &'static str)> = Vec::new();
-    let mut seen = HashSet::new();
+pub async fn get_effective_dns() -> Result<Vec<EffectiveDns>, NetworkError> {
+    let ps_script = r#"
+        Get-NetIPInterface -AddressFamily IPv4 -ConnectionState Connected -ErrorAction SilentlyContinue |
+        Where-Object { $_.InterfaceAlias -notlike 'Loopback*' } |
+        ForEach-Object {
+            $servers = @(
+                Get-DnsClientServerAddress -InterfaceIndex $_.InterfaceIndex -ErrorAction SilentlyContinue |
+                Sort-Object AddressFamily |
+                ForEach-Object { $_.ServerAddresses } |
+                Where-Object { $_ -and $_ -notlike 'fec0:*' }
+            )
+            if ($servers.Count -gt 0) {
+                [PSCustomObject]@{
+                    interface_alias = $_.InterfaceAlias
+                    servers = $servers
+                    metric = [int]$_.InterfaceMetric
+                }
+            }
+        } |
+        ConvertTo-Json -Compress -Depth 3
+    "#;
+    let mut resolvers: Vec<EffectiveDns> = parse_json_rows(&run_powershell(ps_script)?)?
+        .into_iter()
+        .map(|item| {
+            serde_json::from_value(item)
+                .map_err(|e| NetworkError::parse(format!("Unexpected DNS client data: {}", e)))
+        })
+        .collect::<Result<_, _>>()?;
+    resolvers.sort_by_key(|r| r.metric);
+    Ok(resolvers)
+}
 
-    for target in targets {
-        let trimmed = target.trim().to_lowercase();
-        if trimmed.is_empty() {
-            continue;
-        }
+/// Ask a running `clear_cache_targets` to stop before its next target
+#[tauri::command]
+pub async fn cancel_cache_cleanup() -> Result<(), NetworkError> {
+    CACHE_CLEANUP_CANCEL.store(true, Ordering::Relaxed);
+    Ok(())
+}
 
-        let is_safe_token = trimmed
-            .chars()
-            .all(|ch| ch.is_ascii_lowercase() || ch.is_ascii_digit() || ch == '_' || ch == '-');
-        if !is_safe_token {
-            continue;
-        }
+/// Remember the current routing table for a later `diff_routes`. Returns the route count.
+#[tauri::command]
+pub async fn snapshot_routes() -> Result<usize, NetworkError> {
+    let routes = read_routing_table(None)?;
+    let count = routes.len();
+    let mut guard = match ROUTE_SNAPSHOT.lock() {
+        Ok(g) => g,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    *guard = Some(routes);
+    Ok(count)
+}
 
-        if seen.contains(&trimmed) {
-            continue;
-        }
+/// Compare the live routing table against the last snapshot. Routes are matched on
+/// destination, mask and interface; a match with another gateway or metric is "changed".
+#[tauri::command]
+pub async fn diff_routes() -> Result<RouteDiff, NetworkError> {
+    let mut removed = {
+        let guard = match ROUTE_SNAPSHOT.lock() {
+            Ok(g) => g,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        guard.clone().ok_or_else(|| {
+            NetworkError::not_found("No route snapshot taken yet; call snapshot_routes first")
+        })?
+    };
 
-        if let Some((label, script)) = cache_cleanup_recipe(&trimmed) {
-            seen.insert(trimmed.clone());
-            selected.push((trimmed, label, script));
+    // Identical routes cancel out first so duplicates pair up predictably
+    let mut remaining = Vec::new();
+    for route in read_routing_table(None)? {
+        match removed.iter().position(|old| *old == route) {
+            Some(pos) => {
+                removed.remove(pos);
+            }
+            None => remaining.push(route),
         }
     }
 
-    if selected.is_empty() {
-        return Err("No valid cache targets selected".to_string());
+    let mut added = Vec::new();
+    let mut changed = Vec::new();
+    for route in remaining {
+        let same_route = removed.iter().position(|old| {
+            old.destination == route.destination
+                && old.netmask == route.netmask
+                && old.interface_index == route.interface_index
+        });
+        match same_route {
+            Some(pos) => changed.push((removed.remove(pos), route)),
+            None => added.push(route),
+        }
     }
 
-    let mut output_lines = vec![
-        format!("Requested cleanup for {} cache target(s).", selected.len()),
-        "Administrative privileges may be required for some targets.".to_string(),
-        String::new(),
-    ];
-    let mut success_count = 0u32;
-    let mut failed_count = 0u32;
+    Ok(RouteDiff {
+        added,
+        removed,
+        changed,
+    })
+}
 
-    for (_, label, script) in selected {
-        output_lines.push(format!("[TARGET] {}", label));
-        match run_powershell(script) {
-            Ok(raw_output) => {
-                let clean_output = raw_output.trim();
-                if clean_output.is_empty() {
-                    output_lines.push(format!("[OK] {} cleaned.", label));
-                    success_count += 1;
-                } else {
-                    output_lines.extend(clean_output.lines().map(|line| line.trim_end().to_string()));
-                    if clean_output.contains("[FAIL]") {
-                        failed_count += 1;
-                    } else {
-                        success_count += 1;
-                    }
-                }
-            }
-            Err(err) => {
-                failed_count += 1;
-                output_lines.push(format!("[FAIL] {} cleanup error: {}", label, err.trim()));
-            }
-        }
-        output_lines.push(String::new());
+/// Send `count` pings (1..=500) and report nearest-rank latency percentiles over the
+/// replies. All latencies are 0 when nothing answered.
+#[tauri::command]
+pub async fn ping_percentiles(
+    app: tauri::AppHandle,
+    target: String,
+    count: u32,
+) -> Result<PingPercentiles, NetworkError> {
+    let target = target.trim().to_string();
+    if !is_safe_host_token(&target) {
+        return Err(NetworkError::invalid(format!("Invalid target: {}", target)));
     }
+    if !(1..=500).contains(&count) {
+        return Err(NetworkError::invalid(
+            "Ping count must be between 1 and 500",
+        ));
+    }
+    let timeout = load_preferences(&app).ping_timeout_ms.to_string();
 
-    output_lines.push(format!(
-        "Summary: success={} failed={}",
-        success_count, failed_count
-    ));
+    let result = ping_target_from(target, &timeout, count, None);
+    let mut latencies = parse_reply_latencies(&result.output, result.latency_ms);
+    latencies.truncate(count as usize);
+    latencies.sort_unstable();
 
-    Ok(CommandResult {
-        success: failed_count == 0,
-        output: output_lines.join("\n"),
+    let percentile = |p: usize| {
+        if latencies.is_empty() {
+            return 0;
+        }
+        let rank = (p * latencies.len()).div_ceil(100).max(1);
+        latencies[rank - 1]
+    };
+    Ok(PingPercentiles {
+        p50: percentile(50),
+        p90: percentile(90),
+        p99: percentile(99),
+        min: latencies.first().copied().unwrap_or(0),
+        max: latencies.last().copied().unwrap_or(0),
+        loss_percent: result.loss_percent,
     })
 }
 
-/// fping-like scan over multiple targets (parallel ping once per host)
+/// Turn DNS-over-HTTPS on or off for `server` on one interface (Windows 11+).
+/// Registers the server's template first when Windows doesn't know it, then flags the
+/// interface to use it; disabling clears the flag and allows plain DNS again.
 #[tauri::command]
-pub async fn fping_scan(
-    targets: Vec<String>,
-    timeout_ms: Option<u32>,
-) -> Result<FpingScanResult, String> {
-    let timeout = timeout_ms.unwrap_or(1200).clamp(200, 10_000).to_string();
-
-    let clean_targets: Vec<String> = targets
-        .into_iter()
-        .map(|t| t.trim().to_string())
-        .filter(|t| !t.is_empty())
-        .take(512)
-        .collect();
+pub async fn set_doh(
+    interface_index: String,
+    server: String,
+    enabled: bool,
+) -> Result<DohConfig, NetworkError> {
+    require_admin("Configuring DNS-over-HTTPS")?;
 
-    if clean_targets.is_empty() {
-        return Err("No targets provided".to_string());
-    }
+    let idx = validate_interface_index(&interface_index)?;
+    let server = server.trim().to_string();
+    validate_ipv4(&server, "DNS server")?;
+    let alias = interface_alias(&idx)?;
+    let template = DOH_TEMPLATES
+        .iter()
+        .find(|(ip, _)| *ip == server)
+        .map(|(_, template)| *template)
+        .unwrap_or("");
 
-    let cpu_workers = thread::available_parallelism()
-        .map(|n| n.get())
-        .unwrap_or(4);
-    let max_parallel = 24usize;
-    let worker_count = clean_targets
-        .len()
-        .min(max_parallel)
-        .min(cpu_workers.max(1));
+    let script = format!(
+        r#"
+        $ErrorActionPreference = 'Stop'
+        $server = '{server}'
+        $existing = Get-DnsClientDohServerAddress -ServerAddress $server -ErrorAction SilentlyContinue
+        if (-not $existing) {{
+            if (-not '{template}') {{ throw "No DoH template is known for $server" }}
+            Add-DnsClientDohServerAddress -ServerAddress $server -DohTemplate '{template}' -AllowFallbackToUdp $false -AutoUpgrade $true
+        }}
+        Set-DnsClientDohServerAddress -ServerAddress $server -AutoUpgrade ${enabled} -AllowFallbackToUdp (-not ${enabled})
+        $guid = (Get-NetAdapter -InterfaceIndex {idx}).InterfaceGuid
+        $key = "HKLM:\SYSTEM\CurrentControlSet\Services\Dnscache\InterfaceSpecificParameters\$guid\DohInterfaceSettings\Doh\$server"
+        if (${enabled}) {{
+            New-Item -Path $key -Force | Out-Null
+            New-ItemProperty -Path $key -Name DohFlags -Value 1 -PropertyType QWord -Force | Out-Null
+        }} else {{
+            Remove-Item -Path $key -Recurse -Force -ErrorAction SilentlyContinue
+        }}
+        Clear-DnsClientCache
+        $doh = Get-DnsClientDohServerAddress -ServerAddress $server
+        [PSCustomObject]@{{
+            template = $doh.DohTemplate
+            auto_upgrade = [bool]$doh.AutoUpgrade
+            allow_fallback_to_udp = [bool]$doh.AllowFallbackToUdp
+            enabled = [bool](Test-Path $key)
+        }} | ConvertTo-Json -Compress
+    "#,
+        server = server,
+        template = template,
+        idx = idx,
+        enabled = enabled,
+    );
+    let row = parse_json_rows(&run_powershell(&script)?)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| NetworkError::parse("No DoH configuration returned"))?;
 
-    let queue: Arc<Mutex<VecDeque<(usize, String)>>> = Arc::new(Mutex::new(
-        clean_targets
-            .iter()
-            .cloned()
-            .enumerate()
-            .collect::<VecDeque<(usize, String)>>(),
-    ));
-    let results: Arc<Mutex<Vec<(usize, FpingHostResult)>>> =
-        Arc::new(Mutex::new(Vec::with_capacity(clean_targets.len())));
+    Ok(DohConfig {
+        interface_index: idx,
+        interface_alias: alias,
+        server,
+        template: row["template"].as_str().unwrap_or_default().to_string(),
+        auto_upgrade: row["auto_upgrade"].as_bool().unwrap_or(false),
+        allow_fallback_to_udp: row["allow_fallback_to_udp"].as_bool().unwrap_or(false),
+        enabled: row["enabled"].as_bool().unwrap_or(false),
+    })
+}
 
-    let mut workers = Vec::with_capacity(worker_count);
-    for _ in 0..worker_count {
-        let queue_ref = Arc::clone(&queue);
-        let results_ref = Arc::clone(&results);
-        let timeout_clone = timeout.clone();
-        workers.push(thread::spawn(move || loop {
-            let next_job = {
-                let mut guard = match queue_ref.lock() {
-                    Ok(g) => g,
-                    Err(poisoned) => poisoned.into_inner(),
-                };
-                guard.pop_front()
-            };
+/// Loopback self-test of the local TCP/IP stack: ICMP on 127.0.0.1 and ::1 plus a TCP
+/// connect to a throwaway local listener. Failures here mean `netsh int ip reset`
+/// territory rather than an external network problem.
+#[tauri::command]
+pub async fn check_stack_health() -> Result<StackHealth, NetworkError> {
+    let v4 = thread::spawn(|| ping_once_target("127.0.0.1".to_string(), "1000", 1).success);
+    let loopback_v6 = ping_once_target("::1".to_string(), "1000", 1).success;
+    let loopback_v4 = v4.join().unwrap_or(false);
 
-            let (index, target) = match next_job {
-                Some(job) => job,
-                None => break,
-            };
+    // The listener must stay alive for the connect; the backlog accepts it without `accept()`
+    let tcp_loopback = TcpListener::bind("127.0.0.1:0")
+        .and_then(|listener| {
+            let addr = listener.local_addr()?;
+            TcpStream::connect_timeout(&addr, Duration::from_secs(1))
+        })
+        .is_ok();
 
-            let result = ping_once_target(target, &timeout_clone);
-            let mut out_guard = match results_ref.lock() {
-                Ok(g) => g,
-                Err(poisoned) => poisoned.into_inner(),
-            };
-            out_guard.push((index, result));
-        }));
-    }
+    Ok(StackHealth {
+        loopback_v4,
+        loopback_v6,
+        tcp_loopback,
+    })
+}
 
-    for worker in workers {
-        let _ = worker.join();
-    }
+/// Current IPv4 interface metric (NIC priority) of one interface
+#[tauri::command]
+pub async fn get_interface_metric(
+    interface_index: String,
+) -> Result<InterfaceMetric, NetworkError> {
+    let idx = validate_interface_index(&interface_index)?;
+    read_interface_metric(&idx)
+}
 
-    let mut ordered_results = {
-        let guard = match results.lock() {
-            Ok(g) => g,
-            Err(poisoned) => poisoned.into_inner(),
-        };
-        guard.clone()
-    };
-    ordered_results.sort_by_key(|(index, _)| *index);
-    let hosts: Vec<FpingHostResult> = ordered_results
-        .into_iter()
-        .map(|(_, host_result)| host_result)
-        .collect();
+/// Pin the interface metric (1..=9999) or hand it back to Windows with `automatic`.
+/// Applied to IPv4 and IPv6 so both families prefer the same NIC.
+#[tauri::command]
+pub async fn set_interface_metric(
+    interface_index: String,
+    metric: u32,
+    automatic: bool,
+) -> Result<InterfaceMetric, NetworkError> {
+    require_admin("Changing the interface metric")?;
 
-    let sent = hosts.len() as u32;
-    let received = hosts.iter().filter(|h| h.success).count() as u32;
-    let loss_percent = if sent == 0 {
-        100.0
+    let idx = validate_interface_index(&interface_index)?;
+    let setting = if automatic {
+        "-AutomaticMetric Enabled".to_string()
     } else {
-        ((sent - received) as f32 / sent as f32) * 100.0
+        if !(1..=9999).contains(&metric) {
+            return Err(NetworkError::invalid(format!(
+                "Metric must be between 1 and 9999, got {}",
+                metric
+            )));
+        }
+        format!("-InterfaceMetric {}", metric)
     };
+    let script = format!(
+        "Set-NetIPInterface -InterfaceIndex {} -AddressFamily IPv4 {} -ErrorAction Stop; \
+         Set-NetIPInterface -InterfaceIndex {} -AddressFamily IPv6 {} -ErrorAction SilentlyContinue",
+        idx, setting, idx, setting
+    );
+    run_powershell(&script)?;
+    read_interface_metric(&idx)
+}
 
-    let mut min_ms = 0;
-    let mut max_ms = 0;
-    let mut avg_ms = 0;
-    let alive_latencies: Vec<u32> = hosts
-        .iter()
-        .filter(|h| h.success)
-        .map(|h| h.latency_ms)
-        .collect();
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    if !alive_latencies.is_empty() {
-        min_ms = *alive_latencies.iter().min().unwrap_or(&0);
-        max_ms = *alive_latencies.iter().max().unwrap_or(&0);
-        avg_ms = alive_latencies.iter().sum::<u32>() / alive_latencies.len() as u32;
+    #[test]
+    fn validate_interface_index_rejects_empty() {
+        assert!(validate_interface_index("").is_err());
+        assert!(validate_interface_index("   ").is_err());
     }
 
-    Ok(FpingScanResult {
-        sent,
-        received,
-        loss_percent,
-        min_ms,
-        avg_ms,
-        max_ms,
-        hosts,
-    })
-}
+    #[test]
+    fn validate_interface_index_rejects_non_numeric() {
+        assert!(validate_interface_index("12a").is_err());
+        assert!(validate_interface_index("1;calc").is_err());
+        assert!(validate_interface_index("-1").is_err());
+    }
 
-/// Check internet connectivity
-#[tauri::command]
-pub async fn check_internet() -> Result<bool, String> {
-    match TcpStream::connect_timeout(
-        &"8.8.8.8:53".parse().unwrap(),
-        Duration::from_secs(3),
-    ) {
-        Ok(_) => Ok(true),
-        Err(_) => Ok(false),
+    #[test]
+    fn validate_interface_index_trims_whitespace() {
+        assert_eq!(validate_interface_index(" 12 ").unwrap(), "12");
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn run_powershell_round_trips_non_ascii_output() {
+        let expected = "Café – Ünïcødé ✓";
+        let output = run_powershell(&format!("Write-Output '{}'", expected)).unwrap();
+        assert_eq!(output.trim().as_bytes(), expected.as_bytes());
     }
 }
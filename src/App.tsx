@@ -261,6 +261,12 @@ const CACHE_CLEANUP_OPTIONS: CacheCleanupOption[] = [
     description: "Clear D3DSCache",
     defaultChecked: true,
   },
+  {
+    id: "store_cache",
+    label: "Microsoft Store Cache",
+    description: "Run wsreset and clear the Store package cache",
+    defaultChecked: false,
+  },
 ];
 
 const DEFAULT_CACHE_SELECTION = new Set(
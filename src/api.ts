@@ -1,12 +1,48 @@
-import { invoke } from "@tauri-apps/api/core";
+import { invoke as tauriInvoke } from "@tauri-apps/api/core";
 
 // ======================== TYPES ========================
 
+export type NetworkErrorKind =
+  | "not_admin"
+  | "command_failed"
+  | "parse_error"
+  | "invalid_input"
+  | "timeout"
+  | "not_found"
+  | "io";
+
+export interface NetworkError {
+  kind: NetworkErrorKind;
+  message: string;
+  operation?: string;
+  command?: string;
+  stderr?: string;
+  timeout_secs?: number;
+}
+
+/** Thrown by every API call when the backend returns a NetworkError. */
+export class NetworkCommandError extends Error {
+  readonly kind: NetworkErrorKind;
+  readonly detail: NetworkError;
+
+  constructor(detail: NetworkError) {
+    super(detail.message);
+    this.name = "NetworkCommandError";
+    this.kind = detail.kind;
+    this.detail = detail;
+  }
+
+  toString(): string {
+    return this.message;
+  }
+}
+
 export interface NetworkInterface {
   index: string;
   ip: string;
   gateway: string;
   description: string;
+  gateway_latency_ms: number | null;
 }
 
 export interface RouteEntry {
@@ -28,6 +64,9 @@ export interface FpingHostResult {
   success: boolean;
   latency_ms: number;
   output: string;
+  sent: number;
+  received: number;
+  loss_percent: number;
 }
 
 export interface FpingScanResult {
@@ -38,6 +77,9 @@ export interface FpingScanResult {
   avg_ms: number;
   max_ms: number;
   hosts: FpingHostResult[];
+  cancelled: boolean;
+  stopped_early: boolean;
+  workers: number;
 }
 
 export interface CommandResult {
@@ -48,6 +90,8 @@ export interface CommandResult {
 export interface BloatwareItem {
   package_name: string;
   label: string;
+  category: string;
+  vendor: string | null;
   installed: boolean;
 }
 
@@ -55,14 +99,511 @@ export interface BatteryReportResult {
   html: string;
 }
 
+export interface ProxySettings {
+  enabled: boolean;
+  server: string;
+  bypass_list: string[];
+}
+
+export interface SpeedTestResult {
+  download_mbps: number;
+  bytes: number;
+  seconds: number;
+}
+
+export interface RouteSpec {
+  destination: string;
+  mask: string;
+  gateway: string;
+  metric: string;
+  interface_index: string | null;
+}
+
+export interface DefaultGateway {
+  gateway: string;
+  interface_index: string;
+  interface_alias: string;
+  /** route metric + interface metric */
+  metric: number;
+}
+
+export interface AdapterConfig {
+  adapter_name: string;
+  description: string;
+  mac: string;
+  ipv4: string[];
+  ipv6: string[];
+  subnet_mask: string;
+  default_gateway: string[];
+  dns_servers: string[];
+  dhcp_server: string;
+}
+
+export interface DiagnosticStep {
+  name: string;
+  passed: boolean;
+  latency_ms: number | null;
+  detail: string;
+}
+
+export interface NetworkDiagnostics {
+  steps: DiagnosticStep[];
+  default_route_count: number;
+  healthy: boolean;
+  verdict: string;
+}
+
+export interface AuditEntry {
+  timestamp: number;
+  operation: string;
+  parameters: Record<string, unknown>;
+  success: boolean;
+  output: string;
+}
+
+export interface RuntimeCapabilities {
+  degraded: boolean;
+  available_features: string[];
+  unavailable_features: string[];
+}
+
+export interface EnvironmentReport {
+  windows_build: number | null;
+  is_admin: boolean | null;
+  has_webview2: boolean;
+  missing_commands: string[];
+}
+
+export interface CommandAvailability {
+  command: string;
+  available: boolean;
+  path: string | null;
+}
+
+export interface ConnectivityStatus {
+  ipv4: boolean;
+  ipv6: boolean;
+  dns: boolean;
+}
+
+export interface GatewayStatus {
+  gateway: string;
+  reachable: boolean;
+  latency_ms: number;
+}
+
+export interface ServiceLatency {
+  name: string;
+  endpoint: string;
+  latency_ms: number;
+  reachable: boolean;
+}
+
+export interface TcpTestResult {
+  computer: string;
+  remote_address: string;
+  port_open: boolean;
+  ping_succeeded: boolean;
+  ping_latency_ms: number;
+}
+
+export interface DhcpRenewResult {
+  success: boolean;
+  interface_alias: string | null;
+  before_ips: string[];
+  after_ips: string[];
+  output: string;
+}
+
+export interface CacheProgress {
+  target: string;
+  label: string;
+  index: number;
+  total: number;
+  status: "cleaned" | "failed";
+}
+
+export interface BloatwareProgress {
+  package: string;
+  label: string;
+  index: number;
+  total: number;
+  status: "removed" | "skipped" | "failed";
+}
+
+export interface ScheduledTask {
+  name: string;
+  path: string;
+  state: string;
+  description: string;
+}
+
+export interface WindowsService {
+  name: string;
+  display_name: string;
+  status: string;
+  start_type: string;
+}
+
+export interface StartupItem {
+  name: string;
+  command: string;
+  location: string;
+  enabled: boolean;
+}
+
+export interface ReverseDnsResult {
+  ip: string;
+  hostname: string;
+}
+
+export interface LanDevice {
+  ip: string;
+  mac: string;
+  hostname: string;
+  vendor: string | null;
+}
+
+export interface DnsCacheEntry {
+  name: string;
+  record_type: string;
+  ttl: number;
+  data: string;
+}
+
+export interface ConnectionQualitySample {
+  second: number;
+  gateway: GatewayStatus;
+  internet_reachable: boolean;
+  internet_latency_ms: number;
+}
+
+export interface ConnectionQualityReport {
+  samples: number;
+  successful: number;
+  uptime_percent: number;
+  avg_latency_ms: number;
+  max_gap_secs: number;
+}
+
+export interface RouteLookup {
+  route: RouteEntry;
+  source_ip: string;
+  interface_index: string;
+  interface_alias: string;
+}
+
+export interface MtuProbeResult {
+  target: string;
+  mtu: number;
+  largest_payload: number;
+  applied: boolean;
+  output: string;
+}
+
+export interface NeighborEntry {
+  ip: string;
+  mac: string;
+  interface_index: string;
+  state: string;
+}
+
+export interface FirewallProfile {
+  name: string;
+  enabled: boolean;
+}
+
+export interface DnsBenchmark {
+  server: string;
+  latency_ms: number;
+  success: boolean;
+}
+
+export interface PowerPlan {
+  guid: string;
+  name: string;
+  active: boolean;
+}
+
+export interface BatteryStatus {
+  charge_percent: number;
+  on_ac_power: boolean;
+  time_remaining_mins: number | null;
+  charging: boolean;
+}
+
+export interface PingSample {
+  seq: number;
+  success: boolean;
+  latency_ms: number;
+  ttl: number;
+}
+
+export interface IpConflict {
+  ip: string;
+  local_mac: string;
+  conflicting_mac: string | null;
+}
+
+export interface AddressBackup {
+  ip: string;
+  prefix_length: number;
+}
+
+export interface InterfaceConfigBackup {
+  interface_index: string;
+  interface_alias: string;
+  dhcp: boolean;
+  addresses: AddressBackup[];
+  gateway: string | null;
+  dns_static: boolean;
+  dns_servers: string[];
+}
+
+export interface NetworkConfigBackup {
+  created_at: number;
+  interfaces: InterfaceConfigBackup[];
+  persistent_routes: RouteSpec[];
+}
+
+export interface TraceHop {
+  hop: number;
+  address: string;
+  latencies_ms: (number | null)[];
+}
+
+export interface PathReport {
+  target: string;
+  resolved_ip: string;
+  chosen_route: RouteEntry;
+  source_ip: string;
+  interface_alias: string;
+  hops: TraceHop[];
+}
+
+export interface InterfaceAddress {
+  ip: string;
+  prefix_length: number;
+  address_family: string;
+  origin: string;
+}
+
+export interface PortCheck {
+  open: boolean;
+  latency_ms: number;
+}
+
+export interface Ipv6BindingState {
+  interface_index: string;
+  interface_alias: string;
+  enabled: boolean;
+}
+
+export interface InterfaceResetResult {
+  success: boolean;
+  interface_index: string;
+  interface_alias: string;
+  before_ips: string[];
+  after_ips: string[];
+  output: string;
+}
+
+export interface CaptivePortalStatus {
+  captive: boolean;
+  redirect_url: string | null;
+}
+
+export interface WindowsVersion {
+  build: number;
+  display_version: string;
+  edition: string;
+  is_server: boolean;
+}
+
+export interface ProcessInfo {
+  pid: number;
+  name: string;
+  memory_mb: number;
+  cpu_percent: number;
+}
+
+export interface SystemUptime {
+  last_boot: string;
+  uptime_secs: number;
+}
+
+export interface DiskInfo {
+  drive: string;
+  total_bytes: number;
+  free_bytes: number;
+  used_percent: number;
+}
+
+export interface CacheTargetInfo {
+  id: string;
+  label: string;
+  description: string;
+  paths: string[];
+}
+
+export interface SubnetInfo {
+  network: string;
+  broadcast: string;
+  first_host: string;
+  last_host: string;
+  usable_hosts: number;
+  prefix_length: number;
+}
+
+export interface Preferences {
+  ping_timeout_ms: number;
+  fping_max_parallel: number | null;
+  default_interface_index: string | null;
+  confirm_destructive_ops: boolean;
+}
+
+export interface GatewayPreview {
+  on_link: boolean;
+  reachable: boolean;
+  latency_ms: number | null;
+  warnings: string[];
+}
+
+export interface ArpFlushResult {
+  success: boolean;
+  removed: number;
+  output: string;
+}
+
+export interface ProcessNetUsage {
+  pid: number;
+  process_name: string;
+  connection_count: number;
+  listening_ports: number[];
+  established_count: number;
+}
+
+export interface TemporaryRoute {
+  id: string;
+  destination: string;
+  mask: string;
+  gateway: string;
+  expires_at: number;
+}
+
+export interface HostResolution {
+  name: string;
+  addresses: string[];
+  ipv4_addresses: string[];
+  ipv6_addresses: string[];
+  canonical_name: string | null;
+}
+
+export interface RouteAuditResult {
+  route: RouteEntry;
+  interface_exists: boolean;
+  gateway_reachable: boolean | null;
+  issue: string | null;
+}
+
+export interface DnsMonitorSample {
+  second: number;
+  success: boolean;
+  latency_ms: number;
+  error: string | null;
+}
+
+export interface DnsMonitorReport {
+  attempts: number;
+  successes: number;
+  avg_latency_ms: number;
+  failures: string[];
+}
+
+export interface EventLogEntry {
+  time: string;
+  level: string;
+  provider: string;
+  event_id: number;
+  message: string;
+}
+
+export interface PathMtuResult {
+  path_mtu: number;
+  fragmentation_occurred: boolean;
+  /** [packet size, answered] pairs in probe order */
+  probes: [number, boolean][];
+}
+
+export interface EffectiveDns {
+  interface_alias: string;
+  servers: string[];
+  metric: number;
+}
+
+export interface RouteDiff {
+  added: RouteEntry[];
+  removed: RouteEntry[];
+  /** [snapshot, live] pairs */
+  changed: [RouteEntry, RouteEntry][];
+}
+
+export interface PingPercentiles {
+  p50: number;
+  p90: number;
+  p99: number;
+  min: number;
+  max: number;
+  loss_percent: number;
+}
+
+export interface DohConfig {
+  interface_index: string;
+  interface_alias: string;
+  server: string;
+  template: string;
+  auto_upgrade: boolean;
+  allow_fallback_to_udp: boolean;
+  enabled: boolean;
+}
+
+export interface StackHealth {
+  loopback_v4: boolean;
+  loopback_v6: boolean;
+  tcp_loopback: boolean;
+}
+
+export interface InterfaceMetric {
+  interface_index: string;
+  metric: number;
+  automatic: boolean;
+}
+
 // ======================== API CALLS ========================
 
-export async function getNetworkInterfaces(activeOnly: boolean): Promise<NetworkInterface[]> {
-  return invoke<NetworkInterface[]>("get_network_interfaces", { activeOnly });
+async function invoke<T>(command: string, args?: Record<string, unknown>): Promise<T> {
+  try {
+    return await tauriInvoke<T>(command, args);
+  } catch (err) {
+    if (err && typeof err === "object" && "kind" in err && "message" in err) {
+      throw new NetworkCommandError(err as NetworkError);
+    }
+    throw err;
+  }
 }
 
-export async function getRoutingTable(): Promise<RouteEntry[]> {
-  return invoke<RouteEntry[]>("get_routing_table");
+export async function getNetworkInterfaces(
+  activeOnly: boolean,
+  probeGateways?: boolean
+): Promise<NetworkInterface[]> {
+  return invoke<NetworkInterface[]>("get_network_interfaces", {
+    activeOnly,
+    probeGateways: probeGateways ?? null,
+  });
+}
+
+export async function getRoutingTable(interfaceIndex?: string): Promise<RouteEntry[]> {
+  return invoke<RouteEntry[]>("get_routing_table", { interfaceIndex: interfaceIndex || null });
 }
 
 export async function addRoute(
@@ -100,22 +641,44 @@ export async function runNetworkCommand(command: string): Promise<CommandResult>
   return invoke<CommandResult>("run_network_command", { command });
 }
 
-export async function pingHost(target: string, count?: number): Promise<PingResult> {
-  return invoke<PingResult>("ping_host", { target, count: count || null });
+export async function pingHost(
+  target: string,
+  count?: number,
+  sourceIp?: string,
+  timeoutMs?: number
+): Promise<PingResult> {
+  return invoke<PingResult>("ping_host", {
+    target,
+    count: count || null,
+    sourceIp: sourceIp || null,
+    timeoutMs: timeoutMs || null,
+  });
 }
 
 export async function fpingScan(
   targets: string[],
-  timeoutMs?: number
+  timeoutMs?: number,
+  scanId?: string,
+  count?: number,
+  maxParallel?: number,
+  sortBy?: "latency" | "target" | "status",
+  sourceIp?: string,
+  stopAfter?: number
 ): Promise<FpingScanResult> {
   return invoke<FpingScanResult>("fping_scan", {
     targets,
     timeoutMs: timeoutMs || null,
+    scanId: scanId || null,
+    count: count || null,
+    maxParallel: maxParallel || null,
+    sortBy: sortBy || null,
+    sourceIp: sourceIp || null,
+    stopAfter: stopAfter || null,
   });
 }
 
-export async function checkInternet(): Promise<boolean> {
-  return invoke<boolean>("check_internet");
+export async function checkInternet(force?: boolean): Promise<boolean> {
+  return invoke<boolean>("check_internet", { force: force ?? false });
 }
 
 export async function getBloatwareCandidates(): Promise<BloatwareItem[]> {
@@ -126,6 +689,10 @@ export async function removeBloatware(packages: string[]): Promise<CommandResult
   return invoke<CommandResult>("remove_bloatware", { packages });
 }
 
+export async function removeBloatwareCategory(category: string): Promise<CommandResult> {
+  return invoke<CommandResult>("remove_bloatware_category", { category });
+}
+
 export async function clearCacheTargets(targets: string[]): Promise<CommandResult> {
   return invoke<CommandResult>("clear_cache_targets", { targets });
 }
@@ -133,3 +700,450 @@ export async function clearCacheTargets(targets: string[]): Promise<CommandResul
 export async function getBatteryReport(): Promise<BatteryReportResult> {
   return invoke<BatteryReportResult>("get_battery_report");
 }
+
+export async function addFirewallRule(
+  name: string,
+  direction: "inbound" | "outbound",
+  action: "allow" | "block",
+  protocol: "TCP" | "UDP",
+  port: number
+): Promise<CommandResult> {
+  return invoke<CommandResult>("add_firewall_rule", {
+    name,
+    direction,
+    action,
+    protocol,
+    port,
+  });
+}
+
+export async function getProxySettings(): Promise<ProxySettings> {
+  return invoke<ProxySettings>("get_proxy_settings");
+}
+
+export async function setProxySettings(settings: ProxySettings): Promise<ProxySettings> {
+  return invoke<ProxySettings>("set_proxy_settings", { settings });
+}
+
+export async function runSpeedTest(url?: string): Promise<SpeedTestResult> {
+  return invoke<SpeedTestResult>("run_speed_test", { url: url || null });
+}
+
+export async function saveRouteProfile(name: string, routes: RouteSpec[]): Promise<CommandResult> {
+  return invoke<CommandResult>("save_route_profile", { name, routes });
+}
+
+export async function listRouteProfiles(): Promise<string[]> {
+  return invoke<string[]>("list_route_profiles");
+}
+
+export async function applyRouteProfile(
+  name: string,
+  flushConflicting?: boolean
+): Promise<CommandResult> {
+  return invoke<CommandResult>("apply_route_profile", {
+    name,
+    flushConflicting: flushConflicting ?? null,
+  });
+}
+
+export async function getDefaultGateway(): Promise<DefaultGateway> {
+  return invoke<DefaultGateway>("get_default_gateway");
+}
+
+export async function getDefaultGateways(): Promise<DefaultGateway[]> {
+  return invoke<DefaultGateway[]>("get_default_gateways");
+}
+
+export async function getAllowedCommands(): Promise<string[]> {
+  return invoke<string[]>("get_allowed_commands");
+}
+
+export async function getIpConfiguration(): Promise<AdapterConfig[]> {
+  return invoke<AdapterConfig[]>("get_ip_configuration");
+}
+
+export async function runNetworkDiagnostics(): Promise<NetworkDiagnostics> {
+  return invoke<NetworkDiagnostics>("run_network_diagnostics");
+}
+
+export async function cancelFpingScan(scanId: string): Promise<boolean> {
+  return invoke<boolean>("cancel_fping_scan", { scanId });
+}
+
+export async function getRouteAuditLog(): Promise<AuditEntry[]> {
+  return invoke<AuditEntry[]>("get_route_audit_log");
+}
+
+export async function getRuntimeCapabilities(): Promise<RuntimeCapabilities> {
+  return invoke<RuntimeCapabilities>("get_runtime_capabilities");
+}
+
+export async function getEnvironmentReport(): Promise<EnvironmentReport> {
+  return invoke<EnvironmentReport>("get_environment_report");
+}
+
+export async function verifySystemCommands(): Promise<CommandAvailability[]> {
+  return invoke<CommandAvailability[]>("verify_system_commands");
+}
+
+export async function repairNetworkStack(): Promise<CommandResult> {
+  return invoke<CommandResult>("repair_network_stack");
+}
+
+export async function checkConnectivity(): Promise<ConnectivityStatus> {
+  return invoke<ConnectivityStatus>("check_connectivity");
+}
+
+export async function startGatewayMonitor(intervalMs: number): Promise<void> {
+  return invoke<void>("start_gateway_monitor", { intervalMs });
+}
+
+export async function stopGatewayMonitor(): Promise<boolean> {
+  return invoke<boolean>("stop_gateway_monitor");
+}
+
+export async function measureServiceLatency(): Promise<ServiceLatency[]> {
+  return invoke<ServiceLatency[]>("measure_service_latency");
+}
+
+export async function testConnection(host: string, port?: number): Promise<TcpTestResult> {
+  return invoke<TcpTestResult>("test_connection", { host, port: port || null });
+}
+
+export async function renewDhcpLease(interfaceIndex?: string): Promise<DhcpRenewResult> {
+  return invoke<DhcpRenewResult>("renew_dhcp_lease", {
+    interfaceIndex: interfaceIndex || null,
+  });
+}
+
+export async function reinstallAppxPackage(packageName: string): Promise<CommandResult> {
+  return invoke<CommandResult>("reinstall_appx_package", { packageName });
+}
+
+export async function getScheduledTasks(folder?: string): Promise<ScheduledTask[]> {
+  return invoke<ScheduledTask[]>("get_scheduled_tasks", { folder: folder || null });
+}
+
+export async function setScheduledTaskState(
+  path: string,
+  name: string,
+  enabled: boolean
+): Promise<CommandResult> {
+  return invoke<CommandResult>("set_scheduled_task_state", { path, name, enabled });
+}
+
+export async function getServices(nameFilter?: string): Promise<WindowsService[]> {
+  return invoke<WindowsService[]>("get_services", { nameFilter: nameFilter || null });
+}
+
+export async function setServiceState(
+  name: string,
+  action: "start" | "stop" | "restart"
+): Promise<CommandResult> {
+  return invoke<CommandResult>("set_service_state", { name, action });
+}
+
+export async function getStartupItems(): Promise<StartupItem[]> {
+  return invoke<StartupItem[]>("get_startup_items");
+}
+
+export async function setStartupItemEnabled(
+  location: string,
+  name: string,
+  enabled: boolean
+): Promise<CommandResult> {
+  return invoke<CommandResult>("set_startup_item_enabled", { location, name, enabled });
+}
+
+export async function reverseDns(ips: string[]): Promise<ReverseDnsResult[]> {
+  return invoke<ReverseDnsResult[]>("reverse_dns", { ips });
+}
+
+export async function discoverLanDevices(interfaceIndex?: string): Promise<LanDevice[]> {
+  return invoke<LanDevice[]>("discover_lan_devices", { interfaceIndex: interfaceIndex || null });
+}
+
+export async function lookupMacVendor(mac: string): Promise<string | null> {
+  return invoke<string | null>("lookup_mac_vendor", { mac });
+}
+
+export async function getDnsCache(): Promise<DnsCacheEntry[]> {
+  return invoke<DnsCacheEntry[]>("get_dns_cache");
+}
+
+export async function flushDnsCache(): Promise<CommandResult> {
+  return invoke<CommandResult>("flush_dns_cache");
+}
+
+export async function monitorConnectionQuality(durationSecs: number): Promise<ConnectionQualityReport> {
+  return invoke<ConnectionQualityReport>("monitor_connection_quality", { durationSecs });
+}
+
+export async function stopConnectionQualityMonitor(): Promise<boolean> {
+  return invoke<boolean>("stop_connection_quality_monitor");
+}
+
+export async function setRouteMetric(
+  destination: string,
+  mask: string,
+  metric: number
+): Promise<RouteEntry> {
+  return invoke<RouteEntry>("set_route_metric", { destination, mask, metric });
+}
+
+export async function findRouteForDestination(destination: string): Promise<RouteLookup> {
+  return invoke<RouteLookup>("find_route_for_destination", { destination });
+}
+
+export async function exportInterfaces(path: string, format: "csv" | "json"): Promise<CommandResult> {
+  return invoke<CommandResult>("export_interfaces", { path, format });
+}
+
+export async function exportBloatwareReport(path: string, format: "csv" | "json"): Promise<CommandResult> {
+  return invoke<CommandResult>("export_bloatware_report", { path, format });
+}
+
+export async function generateDiagnosticsBundle(path: string): Promise<CommandResult> {
+  return invoke<CommandResult>("generate_diagnostics_bundle", { path });
+}
+
+export async function getPrimaryInterface(): Promise<NetworkInterface | null> {
+  return invoke<NetworkInterface | null>("get_primary_interface");
+}
+
+export async function deleteRoutesTo(destination: string): Promise<CommandResult> {
+  return invoke<CommandResult>("delete_routes_to", { destination });
+}
+
+export async function flushTemporaryRoutes(): Promise<CommandResult> {
+  return invoke<CommandResult>("flush_temporary_routes");
+}
+
+export async function findOptimalMtu(
+  target: string,
+  interfaceIndex: string,
+  apply: boolean
+): Promise<MtuProbeResult> {
+  return invoke<MtuProbeResult>("find_optimal_mtu", { target, interfaceIndex, apply });
+}
+
+export async function getIpv6Neighbors(): Promise<NeighborEntry[]> {
+  return invoke<NeighborEntry[]>("get_ipv6_neighbors");
+}
+
+export async function getFirewallProfiles(): Promise<FirewallProfile[]> {
+  return invoke<FirewallProfile[]>("get_firewall_profiles");
+}
+
+export async function setFirewallProfile(
+  profile: "Domain" | "Private" | "Public",
+  enabled: boolean
+): Promise<FirewallProfile[]> {
+  return invoke<FirewallProfile[]>("set_firewall_profile", { profile, enabled });
+}
+
+export async function benchmarkDns(servers: string[], testDomain: string): Promise<DnsBenchmark[]> {
+  return invoke<DnsBenchmark[]>("benchmark_dns", { servers, testDomain });
+}
+
+export async function getPowerPlans(): Promise<PowerPlan[]> {
+  return invoke<PowerPlan[]>("get_power_plans");
+}
+
+export async function setActivePowerPlan(guid: string): Promise<PowerPlan[]> {
+  return invoke<PowerPlan[]>("set_active_power_plan", { guid });
+}
+
+export async function getBatteryStatus(): Promise<BatteryStatus> {
+  return invoke<BatteryStatus>("get_battery_status");
+}
+
+export async function pingDetailed(target: string, count: number): Promise<PingSample[]> {
+  return invoke<PingSample[]>("ping_detailed", { target, count });
+}
+
+export async function clearNetworkCaches(): Promise<CommandResult> {
+  return invoke<CommandResult>("clear_network_caches");
+}
+
+export async function detectIpConflicts(): Promise<IpConflict[]> {
+  return invoke<IpConflict[]>("detect_ip_conflicts");
+}
+
+export async function backupNetworkConfig(path: string): Promise<CommandResult> {
+  return invoke<CommandResult>("backup_network_config", { path });
+}
+
+export async function restoreNetworkConfig(path: string): Promise<CommandResult> {
+  return invoke<CommandResult>("restore_network_config", { path });
+}
+
+export async function tracePathTo(target: string): Promise<PathReport> {
+  return invoke<PathReport>("trace_path_to", { target });
+}
+
+export async function getInterfaceAddresses(interfaceIndex: string): Promise<InterfaceAddress[]> {
+  return invoke<InterfaceAddress[]>("get_interface_addresses", { interfaceIndex });
+}
+
+export async function isPortOpen(host: string, port: number, timeoutMs?: number): Promise<PortCheck> {
+  return invoke<PortCheck>("is_port_open", { host, port, timeoutMs: timeoutMs || null });
+}
+
+export async function getPersistentRoutes(): Promise<RouteEntry[]> {
+  return invoke<RouteEntry[]>("get_persistent_routes");
+}
+
+export async function setIpv6Enabled(
+  interfaceIndex: string,
+  enabled: boolean
+): Promise<Ipv6BindingState> {
+  return invoke<Ipv6BindingState>("set_ipv6_enabled", { interfaceIndex, enabled });
+}
+
+export async function resetInterface(interfaceIndex: string): Promise<InterfaceResetResult> {
+  return invoke<InterfaceResetResult>("reset_interface", { interfaceIndex });
+}
+
+export async function detectCaptivePortal(): Promise<CaptivePortalStatus> {
+  return invoke<CaptivePortalStatus>("detect_captive_portal");
+}
+
+export async function getWindowsVersion(): Promise<WindowsVersion> {
+  return invoke<WindowsVersion>("get_windows_version");
+}
+
+export async function getProcesses(nameFilter?: string): Promise<ProcessInfo[]> {
+  return invoke<ProcessInfo[]>("get_processes", { nameFilter: nameFilter || null });
+}
+
+export async function killProcess(pid: number): Promise<CommandResult> {
+  return invoke<CommandResult>("kill_process", { pid });
+}
+
+export async function getSystemUptime(): Promise<SystemUptime> {
+  return invoke<SystemUptime>("get_system_uptime");
+}
+
+export async function getDiskUsage(includeAll?: boolean): Promise<DiskInfo[]> {
+  return invoke<DiskInfo[]>("get_disk_usage", { includeAll: includeAll ?? null });
+}
+
+export async function getCacheTargetDetails(): Promise<CacheTargetInfo[]> {
+  return invoke<CacheTargetInfo[]>("get_cache_target_details");
+}
+
+export async function computeSubnetInfo(ip: string, mask: string): Promise<SubnetInfo> {
+  return invoke<SubnetInfo>("compute_subnet_info", { ip, mask });
+}
+
+export async function getPreferences(): Promise<Preferences> {
+  return invoke<Preferences>("get_preferences");
+}
+
+export async function setPreferences(prefs: Preferences): Promise<Preferences> {
+  return invoke<Preferences>("set_preferences", { prefs });
+}
+
+export async function previewDefaultGatewayChange(
+  gateway: string,
+  interfaceIndex: string
+): Promise<GatewayPreview> {
+  return invoke<GatewayPreview>("preview_default_gateway_change", { gateway, interfaceIndex });
+}
+
+export async function flushArpCache(interfaceIndex?: string): Promise<ArpFlushResult> {
+  return invoke<ArpFlushResult>("flush_arp_cache", { interfaceIndex: interfaceIndex || null });
+}
+
+export async function getProcessNetworkUsage(): Promise<ProcessNetUsage[]> {
+  return invoke<ProcessNetUsage[]>("get_process_network_usage");
+}
+
+export async function addTemporaryRoute(
+  destination: string,
+  mask: string,
+  gateway: string,
+  ttlSecs: number
+): Promise<TemporaryRoute> {
+  return invoke<TemporaryRoute>("add_temporary_route", { destination, mask, gateway, ttlSecs });
+}
+
+export async function cancelTemporaryRoute(id: string): Promise<CommandResult> {
+  return invoke<CommandResult>("cancel_temporary_route", { id });
+}
+
+export async function resolveHost(name: string): Promise<HostResolution> {
+  return invoke<HostResolution>("resolve_host", { name });
+}
+
+export async function auditRoutes(): Promise<RouteAuditResult[]> {
+  return invoke<RouteAuditResult[]>("audit_routes");
+}
+
+export async function monitorDns(domain: string, durationSecs: number): Promise<DnsMonitorReport> {
+  return invoke<DnsMonitorReport>("monitor_dns", { domain, durationSecs });
+}
+
+export async function stopDnsMonitor(): Promise<boolean> {
+  return invoke<boolean>("stop_dns_monitor");
+}
+
+export async function getNetworkEventLog(maxEntries: number): Promise<EventLogEntry[]> {
+  return invoke<EventLogEntry[]>("get_network_event_log", { maxEntries });
+}
+
+export async function testPathMtu(target: string): Promise<PathMtuResult> {
+  return invoke<PathMtuResult>("test_path_mtu", { target });
+}
+
+export async function getEffectiveDns(): Promise<EffectiveDns[]> {
+  return invoke<EffectiveDns[]>("get_effective_dns");
+}
+
+export async function runNetworkCommands(commands: string[]): Promise<CommandResult[]> {
+  return invoke<CommandResult[]>("run_network_commands", { commands });
+}
+
+export async function cancelCacheCleanup(): Promise<void> {
+  return invoke<void>("cancel_cache_cleanup");
+}
+
+export type MutationCategory = "route_change" | "nic_config" | "bloatware_removal" | "cache_cleanup";
+
+export async function canPerform(operation: MutationCategory): Promise<boolean> {
+  return invoke<boolean>("can_perform", { operation });
+}
+
+export async function snapshotRoutes(): Promise<number> {
+  return invoke<number>("snapshot_routes");
+}
+
+export async function diffRoutes(): Promise<RouteDiff> {
+  return invoke<RouteDiff>("diff_routes");
+}
+
+export async function pingPercentiles(target: string, count: number): Promise<PingPercentiles> {
+  return invoke<PingPercentiles>("ping_percentiles", { target, count });
+}
+
+export async function setDoh(interfaceIndex: string, server: string, enabled: boolean): Promise<DohConfig> {
+  return invoke<DohConfig>("set_doh", { interfaceIndex, server, enabled });
+}
+
+export async function checkStackHealth(): Promise<StackHealth> {
+  return invoke<StackHealth>("check_stack_health");
+}
+
+export async function getInterfaceMetric(interfaceIndex: string): Promise<InterfaceMetric> {
+  return invoke<InterfaceMetric>("get_interface_metric", { interfaceIndex });
+}
+
+export async function setInterfaceMetric(
+  interfaceIndex: string,
+  metric: number,
+  automatic: boolean,
+): Promise<InterfaceMetric> {
+  return invoke<InterfaceMetric>("set_interface_metric", { interfaceIndex, metric, automatic });
+}